@@ -0,0 +1,149 @@
+use crate::carte::{generer_carte, Carte, ParametresGeneration, TypePixel, HAUTEUR_CARTE, LARGEUR_CARTE};
+use crate::simulation::{EntitePartie, HorlogeSimulation, PhaseJournee, SeedCarte, TickSimulation};
+use crate::theme::{ThemeCouleurs, TileTheme};
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+// Taille d'affichage d'une case en pixels
+pub const TAILLE_CASE: f32 = 20.0;
+
+// Facteur de luminosité appliqué aux couleurs de la carte pendant la nuit
+const LUMINOSITE_NUIT: f32 = 0.4;
+
+// Nombre de ticks entre deux captures par défaut avec --record
+pub const INTERVALLE_CAPTURE: u32 = 10;
+
+/// Composant Bevy pour les entités représentant un pixel de la carte
+#[derive(Component)]
+pub struct Pixel {
+    type_pixel: TypePixel,
+}
+
+/// Ressource décrivant l'enregistrement de la carte en images PNG numérotées
+#[derive(Resource)]
+pub struct EnregistrementImages {
+    dossier: String,
+    // Nombre de ticks de simulation entre deux captures
+    intervalle: u32,
+    // Tick à partir duquel la prochaine image est capturée
+    prochain_tick: u64,
+    // Numéro de la prochaine image
+    numero: u32,
+}
+
+impl EnregistrementImages {
+    /// Enregistrement dans un dossier existant, une image toutes les `intervalle` ticks
+    pub fn new(dossier: String, intervalle: u32) -> EnregistrementImages {
+        EnregistrementImages {
+            dossier,
+            intervalle,
+            prochain_tick: intervalle as u64,
+            numero: 0,
+        }
+    }
+}
+
+/// génère la carte et crée les entités Bevy qui l'affichent
+pub fn generer_map(
+    mut commandes: Commands,
+    seed_carte: Res<SeedCarte>,
+    parametres: Res<ParametresGeneration>,
+    theme: Res<TileTheme>,
+    couleurs: Res<ThemeCouleurs>,
+    carte_chargee: Option<Res<Carte>>,
+) {
+    let carte = match carte_chargee {
+        Some(carte) => carte.clone(),
+        None => generer_carte(seed_carte.seed, &parametres),
+    };
+
+    // 🔹 Création des entités Bevy pour afficher la carte
+    for (coord, type_pixel) in carte.cases() {
+        commandes.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: theme.couleur_base(type_pixel, &couleurs),
+                custom_size: Some(Vec2::splat(TAILLE_CASE)),
+                ..Default::default()
+            },
+            texture: theme.textures.get(&type_pixel).cloned().unwrap_or_default(),
+            transform: Transform::from_translation(Vec3::new(
+                coord.x as f32 * TAILLE_CASE - (LARGEUR_CARTE as f32 * TAILLE_CASE) / 2.0,
+                coord.y as f32 * TAILLE_CASE - (HAUTEUR_CARTE as f32 * TAILLE_CASE) / 2.0,
+                0.0,
+            )),
+            ..Default::default()
+        })
+            .insert((Pixel { type_pixel }, EntitePartie));
+    }
+
+    commandes.insert_resource(carte);
+}
+
+/// Assombrit ou éclaire la carte selon la phase courante du cycle jour/nuit
+pub fn appliquer_lumiere_ambiante(
+    horloge: Res<HorlogeSimulation>,
+    theme: Res<TileTheme>,
+    couleurs: Res<ThemeCouleurs>,
+    mut derniere_phase: Local<Option<PhaseJournee>>,
+    mut couleur_fond: ResMut<ClearColor>,
+    mut pixels: Query<(&Pixel, &mut Sprite)>,
+) {
+    // Les sprites ne sont recolorés qu'au changement de phase
+    if *derniere_phase == Some(horloge.phase) {
+        return;
+    }
+
+    let luminosite = match horloge.phase {
+        PhaseJournee::Jour => 1.0,
+        PhaseJournee::Nuit => LUMINOSITE_NUIT,
+    };
+
+    for (pixel, mut sprite) in pixels.iter_mut() {
+        let couleur = theme.couleur_base(pixel.type_pixel, &couleurs);
+        sprite.color = assombrir(couleur, luminosite);
+    }
+    couleur_fond.0 = assombrir(couleurs.fond, luminosite);
+
+    *derniere_phase = Some(horloge.phase);
+}
+
+/// Multiplie les composantes rouge, verte et bleue par la luminosité, en gardant la transparence du thème
+fn assombrir(couleur: Color, luminosite: f32) -> Color {
+    Color::rgba(
+        couleur.r() * luminosite,
+        couleur.g() * luminosite,
+        couleur.b() * luminosite,
+        couleur.a(),
+    )
+}
+
+/// Capture la fenêtre dans le dossier d'enregistrement toutes les `intervalle` ticks de simulation
+pub fn capturer_image(
+    enregistrement: Option<ResMut<EnregistrementImages>>,
+    tick: Res<TickSimulation>,
+    fenetre: Query<Entity, With<PrimaryWindow>>,
+    mut captures: ResMut<ScreenshotManager>,
+) {
+    let Some(mut enregistrement) = enregistrement else {
+        return;
+    };
+    let Ok(fenetre) = fenetre.get_single() else {
+        return;
+    };
+
+    // Le compteur de ticks repart à chaque nouvelle partie (de zéro, ou du tick d'une sauvegarde)
+    if tick.is_added() {
+        enregistrement.prochain_tick = tick.0 + enregistrement.intervalle as u64;
+    }
+    if tick.0 < enregistrement.prochain_tick {
+        return;
+    }
+
+    let chemin = format!("{}/frame_{:05}.png", enregistrement.dossier, enregistrement.numero);
+    // Une capture encore en cours sur cette fenêtre : on réessaie à la frame suivante
+    if captures.save_screenshot_to_disk(fenetre, &chemin).is_ok() {
+        enregistrement.prochain_tick = tick.0 + enregistrement.intervalle as u64;
+        enregistrement.numero += 1;
+    }
+}
@@ -0,0 +1,123 @@
+use crate::carte::{Coord, HAUTEUR_CARTE, LARGEUR_CARTE};
+use crate::affichage::TAILLE_CASE;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+// Vitesse de déplacement au clavier en pixels par seconde (à zoom 1)
+const VITESSE_CAMERA: f32 = 500.0;
+
+// Facteur de zoom appliqué par cran de molette
+const PAS_ZOOM: f32 = 0.1;
+
+// Bornes du zoom (échelle de la projection : plus petit = plus proche)
+const ZOOM_MIN: f32 = 0.25;
+const ZOOM_MAX: f32 = 2.0;
+
+/// Crée la caméra qui affiche la carte
+pub fn creer_camera(mut commandes: Commands) {
+    commandes.spawn(Camera2dBundle::default());
+}
+
+/// Rectangle de la carte dans le monde (coin inférieur gauche et taille)
+/// Les sprites sont centrés sur leur case, d'où le décalage d'une demi-case
+pub fn rectangle_carte() -> Rect {
+    let largeur = LARGEUR_CARTE as f32 * TAILLE_CASE;
+    let hauteur = HAUTEUR_CARTE as f32 * TAILLE_CASE;
+    let coin = Vec2::new(-largeur / 2.0, -hauteur / 2.0) - Vec2::splat(TAILLE_CASE / 2.0);
+    Rect::from_corners(coin, coin + Vec2::new(largeur, hauteur))
+}
+
+/// Case de la carte située sous le curseur de la souris, s'il est au-dessus de la carte
+pub fn case_sous_curseur(fenetre: &Window, camera: &Camera, transform_camera: &GlobalTransform) -> Option<Coord> {
+    let position = fenetre.cursor_position()?;
+    let monde = camera.viewport_to_world_2d(transform_camera, position)?;
+    let relative = (monde - rectangle_carte().min) / TAILLE_CASE;
+    let case = Coord::new(relative.x.floor() as i32, relative.y.floor() as i32);
+
+    let sur_la_carte =
+        (0..LARGEUR_CARTE as i32).contains(&case.x) && (0..HAUTEUR_CARTE as i32).contains(&case.y);
+    sur_la_carte.then_some(case)
+}
+
+/// Déplace la caméra avec ZQSD/WASD ou les flèches
+pub fn deplacer_camera_clavier(
+    temps: Res<Time>,
+    clavier: Res<Input<KeyCode>>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let mut direction = Vec2::ZERO;
+    if clavier.any_pressed([KeyCode::W, KeyCode::Z, KeyCode::Up]) {
+        direction.y += 1.0;
+    }
+    if clavier.any_pressed([KeyCode::S, KeyCode::Down]) {
+        direction.y -= 1.0;
+    }
+    if clavier.any_pressed([KeyCode::A, KeyCode::Q, KeyCode::Left]) {
+        direction.x -= 1.0;
+    }
+    if clavier.any_pressed([KeyCode::D, KeyCode::Right]) {
+        direction.x += 1.0;
+    }
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    for (mut transform, projection) in cameras.iter_mut() {
+        let deplacement = direction.normalize() * VITESSE_CAMERA * projection.scale * temps.delta_seconds();
+        transform.translation += deplacement.extend(0.0);
+    }
+}
+
+/// Fait glisser la caméra en maintenant le bouton gauche de la souris
+pub fn deplacer_camera_souris(
+    boutons: Res<Input<MouseButton>>,
+    mut mouvements: EventReader<MouseMotion>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let delta: Vec2 = mouvements.read().map(|mouvement| mouvement.delta).sum();
+    if !boutons.pressed(MouseButton::Left) || delta == Vec2::ZERO {
+        return;
+    }
+
+    for (mut transform, projection) in cameras.iter_mut() {
+        // L'axe y de l'écran est orienté vers le bas, celui du monde vers le haut
+        transform.translation.x -= delta.x * projection.scale;
+        transform.translation.y += delta.y * projection.scale;
+    }
+}
+
+/// Zoome ou dézoome avec la molette de la souris
+pub fn zoomer_camera(
+    mut molette: EventReader<MouseWheel>,
+    mut projections: Query<&mut OrthographicProjection, With<Camera2d>>,
+) {
+    let crans: f32 = molette
+        .read()
+        .map(|evenement| match evenement.unit {
+            MouseScrollUnit::Line => evenement.y,
+            // Les pavés tactiles envoient des pixels : environ 100 pixels par cran
+            MouseScrollUnit::Pixel => evenement.y / 100.0,
+        })
+        .sum();
+    if crans == 0.0 {
+        return;
+    }
+
+    for mut projection in projections.iter_mut() {
+        projection.scale = (projection.scale * (1.0 - PAS_ZOOM).powf(crans)).clamp(ZOOM_MIN, ZOOM_MAX);
+    }
+}
+
+/// Empêche le centre de la caméra de sortir de la carte
+pub fn limiter_camera(mut cameras: Query<&mut Transform, (With<Camera2d>, Changed<Transform>)>) {
+    let limites = rectangle_carte();
+
+    for mut transform in cameras.iter_mut() {
+        let centre = transform.translation.truncate().clamp(limites.min, limites.max);
+        // Ne modifie la position que si nécessaire, pour ne pas déclencher Changed à chaque frame
+        if centre != transform.translation.truncate() {
+            transform.translation.x = centre.x;
+            transform.translation.y = centre.y;
+        }
+    }
+}
@@ -0,0 +1,896 @@
+use crate::langue::tr;
+use bevy::prelude::*;
+use noise::{NoiseFn, Perlin};
+use rand::{prelude::*, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+
+// Paramètres de la carte
+pub const LARGEUR_CARTE: usize = 50;
+pub const HAUTEUR_CARTE: usize = 30;
+
+// Seuil de bruit définissant les obstacles (plus bas = plus d'obstacles)
+// Valeur utilisée par la difficulté normale
+const SEUIL_OBSTACLE: f64 = 0.5;
+
+// Echelle appliquée aux coordonnées avant l'échantillonnage du bruit des obstacles
+// (plus petit = obstacles plus larges et plus espacés)
+pub const ECHELLE_BRUIT: f64 = 0.1;
+
+// Seuil de bruit définissant les zones dangereuses (radiations/acide)
+// Valeur utilisée par la difficulté normale
+const SEUIL_ZONE_DANGER: f64 = 0.6;
+
+// Taille maximale des obstacles en pixels connectés
+// Pour éviter d'avoir des obstacles trop grands.
+const MAX_TAILLE_OBSTACLE: usize = 5;
+
+// Nombre maximal de stations pouvant être placées sur la carte
+pub const MAX_STATIONS: usize = 10;
+
+// Rayon (en cases) de la zone dégagée autour de chaque station
+pub const RAYON_ZONE_SURE: usize = 2;
+pub const MAX_RAYON_ZONE_SURE: usize = 5;
+
+// Déplacements vers les quatre cases adjacentes
+const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// Enumération des types de pixel présents sur la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TypePixel {
+    Vide,
+    Obstacle,
+    Energie,
+    Minerai,
+    SiteScientifique,
+    Station,
+    ZoneDanger,
+}
+
+impl TypePixel {
+    /// Tous les types de pixel, dans l'ordre de l'énumération
+    pub const TOUS: [TypePixel; 7] = [
+        TypePixel::Vide,
+        TypePixel::Obstacle,
+        TypePixel::Energie,
+        TypePixel::Minerai,
+        TypePixel::SiteScientifique,
+        TypePixel::Station,
+        TypePixel::ZoneDanger,
+    ];
+
+    /// Nom du type de pixel dans les fichiers (textures, thème de couleurs)
+    pub fn nom(self) -> &'static str {
+        match self {
+            TypePixel::Vide => "vide",
+            TypePixel::Obstacle => "obstacle",
+            TypePixel::Energie => "energie",
+            TypePixel::Minerai => "minerai",
+            TypePixel::SiteScientifique => "site_scientifique",
+            TypePixel::Station => "station",
+            TypePixel::ZoneDanger => "zone_danger",
+        }
+    }
+}
+
+/// Coordonnées d'une case de la carte
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coord {
+    pub fn new(x: i32, y: i32) -> Coord {
+        Coord { x, y }
+    }
+}
+
+/// Ressource contenant la carte générée
+/// Tous les accès passent par des coordonnées `Coord`, vérifiées (ou repliées si la carte est torique)
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct Carte {
+    // Cases stockées ligne par ligne dans un seul tableau
+    donnees: Vec<TypePixel>,
+    // Carte torique : les bords opposés sont reliés entre eux
+    torique: bool,
+    // Composantes connexes calculées à la demande, invalidées à chaque modification de case
+    #[serde(skip)]
+    composantes: OnceLock<ComposantesConnexes>,
+}
+
+/// Régions de cases praticables reliées entre elles
+#[derive(Clone)]
+pub struct ComposantesConnexes {
+    // Etiquette de la composante de chaque case (None pour un obstacle)
+    etiquettes: Vec<Option<usize>>,
+    // Nombre de cases de chaque composante
+    tailles: Vec<usize>,
+    // Repris de la carte, pour replier les coordonnées comme `Carte::get`
+    torique: bool,
+}
+
+impl ComposantesConnexes {
+    /// Etiquette de la composante contenant la case, ou None si elle est hors carte ou infranchissable
+    pub fn composante(&self, coord: Coord) -> Option<usize> {
+        Carte::replier(coord, self.torique).and_then(|case| self.etiquettes[Carte::index(case)])
+    }
+
+    /// Nombre de cases de la composante
+    pub fn taille(&self, composante: usize) -> usize {
+        self.tailles[composante]
+    }
+
+    /// Etiquette de la composante contenant le plus de cases
+    pub fn plus_grande(&self) -> Option<usize> {
+        (0..self.tailles.len()).max_by_key(|&composante| self.tailles[composante])
+    }
+}
+
+impl Carte {
+    /// Crée une carte entièrement vide
+    pub fn new(torique: bool) -> Carte {
+        Carte {
+            donnees: vec![TypePixel::Vide; LARGEUR_CARTE * HAUTEUR_CARTE],
+            torique,
+            composantes: OnceLock::new(),
+        }
+    }
+
+    /// Vérifie que les données correspondent aux dimensions de la carte (utile après un chargement)
+    pub fn dimensions_valides(&self) -> bool {
+        self.donnees.len() == LARGEUR_CARTE * HAUTEUR_CARTE
+    }
+
+    /// Ramène une coordonnée sur la carte : repliée si la carte est torique, None si elle en sort
+    pub fn normaliser(&self, coord: Coord) -> Option<Coord> {
+        Carte::replier(coord, self.torique)
+    }
+
+    /// Normalisation d'une coordonnée, partagée avec les composantes connexes
+    fn replier(coord: Coord, torique: bool) -> Option<Coord> {
+        let (largeur, hauteur) = (LARGEUR_CARTE as i32, HAUTEUR_CARTE as i32);
+
+        if torique {
+            Some(Coord::new(coord.x.rem_euclid(largeur), coord.y.rem_euclid(hauteur)))
+        } else if (0..largeur).contains(&coord.x) && (0..hauteur).contains(&coord.y) {
+            Some(coord)
+        } else {
+            None
+        }
+    }
+
+    /// Position dans `donnees` d'une case déjà normalisée
+    fn index(case: Coord) -> usize {
+        case.y as usize * LARGEUR_CARTE + case.x as usize
+    }
+
+    /// Retourne le type de la case, ou None si elle est hors de la carte
+    pub fn get(&self, coord: Coord) -> Option<TypePixel> {
+        self.normaliser(coord).map(|case| self.donnees[Carte::index(case)])
+    }
+
+    /// Modifie le type de la case (sans effet si elle est hors de la carte)
+    pub fn set(&mut self, coord: Coord, type_pixel: TypePixel) {
+        if let Some(case) = self.normaliser(coord) {
+            let index = Carte::index(case);
+            if self.donnees[index] != type_pixel {
+                self.donnees[index] = type_pixel;
+                self.composantes = OnceLock::new();
+            }
+        }
+    }
+
+    /// Cases adjacentes (haut, bas, gauche, droite) présentes sur la carte
+    pub fn voisins(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        DIRECTIONS
+            .iter()
+            .filter_map(move |&(dx, dy)| self.normaliser(Coord::new(coord.x + dx, coord.y + dy)))
+    }
+
+    /// Coordonnées de toutes les cases, ligne par ligne
+    pub fn coordonnees(&self) -> impl Iterator<Item = Coord> {
+        (0..HAUTEUR_CARTE as i32).flat_map(|y| (0..LARGEUR_CARTE as i32).map(move |x| Coord::new(x, y)))
+    }
+
+    /// Parcourt toutes les cases avec leur type, ligne par ligne
+    pub fn cases(&self) -> impl Iterator<Item = (Coord, TypePixel)> + '_ {
+        self.coordonnees().zip(self.donnees.iter().copied())
+    }
+
+    /// Composantes connexes des cases praticables (tout sauf les obstacles)
+    /// Le résultat est mis en cache jusqu'à la prochaine modification de la carte
+    pub fn composantes_connexes(&self) -> &ComposantesConnexes {
+        self.composantes.get_or_init(|| self.calculer_composantes())
+    }
+
+    /// Etiquette les régions praticables par des parcours en largeur successifs
+    fn calculer_composantes(&self) -> ComposantesConnexes {
+        let mut etiquettes = vec![None; LARGEUR_CARTE * HAUTEUR_CARTE];
+        let mut tailles = Vec::new();
+        let mut file = VecDeque::new();
+
+        for (depart, type_pixel) in self.cases() {
+            if type_pixel == TypePixel::Obstacle || etiquettes[Carte::index(depart)].is_some() {
+                continue;
+            }
+
+            let etiquette = tailles.len();
+            let mut taille = 0;
+            etiquettes[Carte::index(depart)] = Some(etiquette);
+            file.push_back(depart);
+
+            while let Some(case) = file.pop_front() {
+                taille += 1;
+                for voisin in self.voisins(case) {
+                    let index = Carte::index(voisin);
+                    if etiquettes[index].is_none() && self.donnees[index] != TypePixel::Obstacle {
+                        etiquettes[index] = Some(etiquette);
+                        file.push_back(voisin);
+                    }
+                }
+            }
+
+            tailles.push(taille);
+        }
+
+        ComposantesConnexes {
+            etiquettes,
+            tailles,
+            torique: self.torique,
+        }
+    }
+
+    /// Distance de Chebyshev entre deux cases, en passant par les bords si la carte est torique
+    pub fn distance(&self, a: Coord, b: Coord) -> usize {
+        let mut dx = a.x.abs_diff(b.x) as usize;
+        let mut dy = a.y.abs_diff(b.y) as usize;
+
+        if self.torique {
+            dx = dx.min(LARGEUR_CARTE - dx);
+            dy = dy.min(HAUTEUR_CARTE - dy);
+        }
+
+        dx.max(dy)
+    }
+}
+
+/// Enumération des niveaux de difficulté prédéfinis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulte {
+    Facile,
+    Normal,
+    Difficile,
+}
+
+impl Difficulte {
+    /// Retourne la difficulté correspondant au nom donné en argument
+    pub fn depuis_nom(nom: &str) -> Option<Difficulte> {
+        match nom {
+            "facile" => Some(Difficulte::Facile),
+            "normal" => Some(Difficulte::Normal),
+            "difficile" => Some(Difficulte::Difficile),
+            _ => None,
+        }
+    }
+}
+
+/// Enumération des stratégies de placement des stations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlacementStation {
+    // Case vide tirée au hasard
+    Aleatoire,
+    // Case vide la plus proche du centre de la carte
+    Centre,
+    // Case vide de la plus grande région praticable, la plus proche de son centre
+    MeilleureConnectivite,
+}
+
+impl PlacementStation {
+    /// Retourne la stratégie correspondant au nom donné en argument
+    pub fn depuis_nom(nom: &str) -> Option<PlacementStation> {
+        match nom {
+            "aleatoire" => Some(PlacementStation::Aleatoire),
+            "centre" => Some(PlacementStation::Centre),
+            "meilleure-connectivite" => Some(PlacementStation::MeilleureConnectivite),
+            _ => None,
+        }
+    }
+}
+
+/// Ressource regroupant les paramètres de génération de la carte
+#[derive(Resource)]
+pub struct ParametresGeneration {
+    pub nb_stations: usize,
+    // Carte torique : les bords opposés sont reliés entre eux
+    pub torique: bool,
+    pub rayon_zone_sure: usize,
+    pub placement_station: PlacementStation,
+    pub seuil_obstacle: f64,
+    pub echelle_bruit: f64,
+    pub seuil_zone_danger: f64,
+    // Pourcentages de chance d'apparition sur une case vide
+    pub pourcentage_energie: u32,
+    pub pourcentage_minerai: u32,
+    pub pourcentage_site_scientifique: u32,
+}
+
+impl ParametresGeneration {
+    /// Paramètres associés à un niveau de difficulté
+    pub fn depuis_difficulte(difficulte: Difficulte) -> ParametresGeneration {
+        let (seuil_obstacle, seuil_zone_danger, energie, minerai, site_scientifique) = match difficulte {
+            Difficulte::Facile => (0.6, 0.75, 8, 7, 5),
+            Difficulte::Normal => (SEUIL_OBSTACLE, SEUIL_ZONE_DANGER, 6, 5, 4),
+            Difficulte::Difficile => (0.4, 0.45, 4, 3, 3),
+        };
+
+        ParametresGeneration {
+            nb_stations: 1,
+            torique: false,
+            rayon_zone_sure: RAYON_ZONE_SURE,
+            placement_station: PlacementStation::Aleatoire,
+            seuil_obstacle,
+            echelle_bruit: ECHELLE_BRUIT,
+            seuil_zone_danger,
+            pourcentage_energie: energie,
+            pourcentage_minerai: minerai,
+            pourcentage_site_scientifique: site_scientifique,
+        }
+    }
+
+    /// Paramètres d'une difficulté, modifiés par les réglages passés en ligne de commande
+    pub fn avec_reglages(difficulte: Difficulte, reglages: &ReglagesGeneration) -> ParametresGeneration {
+        let mut parametres = ParametresGeneration::depuis_difficulte(difficulte);
+        parametres.nb_stations = reglages.nb_stations.unwrap_or(parametres.nb_stations);
+        parametres.rayon_zone_sure = reglages.rayon_zone_sure.unwrap_or(parametres.rayon_zone_sure);
+        parametres.seuil_obstacle = reglages.seuil_obstacle.unwrap_or(parametres.seuil_obstacle);
+        parametres.echelle_bruit = reglages.echelle_bruit.unwrap_or(parametres.echelle_bruit);
+        parametres.placement_station = reglages.placement_station.unwrap_or(parametres.placement_station);
+        parametres.torique = reglages.torique;
+        parametres
+    }
+}
+
+/// Réglages de génération choisis au lancement, qui remplacent ceux de la difficulté
+/// Les valeurs absentes gardent celles de la difficulté choisie dans le menu
+#[derive(Debug, Clone, Default)]
+pub struct ReglagesGeneration {
+    pub nb_stations: Option<usize>,
+    pub rayon_zone_sure: Option<usize>,
+    pub seuil_obstacle: Option<f64>,
+    pub echelle_bruit: Option<f64>,
+    pub placement_station: Option<PlacementStation>,
+    pub torique: bool,
+}
+
+/// génère la carte avec les obstacles, les zones dangereuses, les ressources et les stations
+pub fn generer_carte(seed: u64, parametres: &ParametresGeneration) -> Carte {
+    let _span = info_span!("generer_carte", seed).entered();
+
+    let bruit_perlin = Perlin::new(seed as u32);
+    let mut generateur_aleatoire = StdRng::seed_from_u64(seed);
+
+    let mut carte = Carte::new(parametres.torique);
+
+    // Génération des obstacles en utilisant le bruit de Perlin
+    for coord in carte.coordonnees() {
+        let valeur_bruit = bruit_perlin.get([
+            coord.x as f64 * parametres.echelle_bruit,
+            coord.y as f64 * parametres.echelle_bruit,
+        ]);
+
+        if valeur_bruit > parametres.seuil_obstacle {
+            carte.set(coord, TypePixel::Obstacle);
+        }
+    }
+
+    // Limite la taille des obstacles pour éviter des zones trop grandes
+    limiter_taille_obstacles(&mut carte);
+
+    // Génération des zones dangereuses avec un second bruit de Perlin indépendant
+    let bruit_danger = Perlin::new(seed.wrapping_add(1) as u32);
+    for coord in carte.coordonnees() {
+        let valeur_bruit = bruit_danger.get([coord.x as f64 * 0.15, coord.y as f64 * 0.15]);
+
+        if carte.get(coord) == Some(TypePixel::Vide) && valeur_bruit > parametres.seuil_zone_danger {
+            carte.set(coord, TypePixel::ZoneDanger);
+        }
+    }
+
+    // Ajout aléatoire des ressources sur les pixel vides
+    // Les pourcentages sont cumulés pour découper le tirage entre 0 et 100
+    let seuil_energie = parametres.pourcentage_energie;
+    let seuil_minerai = seuil_energie + parametres.pourcentage_minerai;
+    let seuil_site_scientifique = seuil_minerai + parametres.pourcentage_site_scientifique;
+    for coord in carte.coordonnees() {
+        if carte.get(coord) == Some(TypePixel::Vide) {
+            let tirage = generateur_aleatoire.gen_range(0..100);
+            let type_pixel = if tirage < seuil_energie {
+                TypePixel::Energie
+            } else if tirage < seuil_minerai {
+                TypePixel::Minerai
+            } else if tirage < seuil_site_scientifique {
+                TypePixel::SiteScientifique
+            } else {
+                TypePixel::Vide
+            };
+            carte.set(coord, type_pixel);
+        }
+    }
+
+    // Placement des stations sur des cases vides
+    let mut stations = Vec::with_capacity(parametres.nb_stations);
+    for numero in 1..=parametres.nb_stations {
+        let station = placer_station(&mut carte, &mut generateur_aleatoire, parametres, &stations);
+        info!(numero, x = station.x, y = station.y, "{}", tr("log.station_placee"));
+        stations.push(station);
+    }
+
+    // Taille de la région accessible depuis chaque station
+    let composantes = carte.composantes_connexes();
+    for &coord in &stations {
+        if let Some(composante) = composantes.composante(coord) {
+            info!(
+                x = coord.x,
+                y = coord.y,
+                cases_accessibles = composantes.taille(composante),
+                "{}", tr("log.region_accessible")
+            );
+        }
+    }
+
+    carte
+}
+
+/// Tire une case au hasard sur la carte
+fn case_aleatoire(generateur_aleatoire: &mut StdRng) -> Coord {
+    // Tirage en usize pour conserver les mêmes cartes qu'avant l'introduction de Coord
+    let x = generateur_aleatoire.gen_range(0..LARGEUR_CARTE);
+    let y = generateur_aleatoire.gen_range(0..HAUTEUR_CARTE);
+    Coord::new(x as i32, y as i32)
+}
+
+/// Choisit la case d'une nouvelle station selon la stratégie de placement
+fn choisir_case_station(
+    carte: &Carte,
+    generateur_aleatoire: &mut StdRng,
+    parametres: &ParametresGeneration,
+    stations: &[Coord],
+) -> Coord {
+    let case_choisie = match parametres.placement_station {
+        PlacementStation::Aleatoire => None,
+        PlacementStation::Centre => {
+            let centre = ((LARGEUR_CARTE - 1) as f64 / 2.0, (HAUTEUR_CARTE - 1) as f64 / 2.0);
+            case_vide_la_plus_proche(carte, centre, None, stations, parametres.rayon_zone_sure)
+        }
+        PlacementStation::MeilleureConnectivite => {
+            let composantes = carte.composantes_connexes();
+            composantes.plus_grande().and_then(|composante| {
+                let centre = centre_de_gravite(carte, composante);
+                case_vide_la_plus_proche(carte, centre, Some(composante), stations, parametres.rayon_zone_sure)
+            })
+        }
+    };
+
+    // Tirage aléatoire par défaut, ou si la stratégie n'a trouvé aucune case
+    case_choisie.unwrap_or_else(|| loop {
+        let coord = case_aleatoire(generateur_aleatoire);
+
+        if carte.get(coord) == Some(TypePixel::Vide) {
+            break coord;
+        }
+    })
+}
+
+/// Centre de gravité des cases d'une composante
+/// Sur une carte torique, la moyenne est circulaire pour qu'une région à cheval sur un bord reste centrée sur elle
+fn centre_de_gravite(carte: &Carte, composante: usize) -> (f64, f64) {
+    let composantes = carte.composantes_connexes();
+    let cases: Vec<Coord> = carte
+        .coordonnees()
+        .filter(|&coord| composantes.composante(coord) == Some(composante))
+        .collect();
+
+    let moyenne = |valeurs: Vec<f64>, taille: usize| {
+        let arithmetique = valeurs.iter().sum::<f64>() / valeurs.len() as f64;
+        if !carte.torique {
+            return arithmetique;
+        }
+
+        // Chaque position devient un angle sur un cercle de périmètre `taille`
+        let echelle = std::f64::consts::TAU / taille as f64;
+        let (somme_cos, somme_sin) = valeurs
+            .iter()
+            .fold((0.0, 0.0), |(c, s), &valeur| (c + (valeur * echelle).cos(), s + (valeur * echelle).sin()));
+        // Région répartie sur tout le tour (une ligne complète par exemple) : aucune direction privilégiée
+        if somme_cos.hypot(somme_sin) < 1e-6 * valeurs.len() as f64 {
+            return arithmetique;
+        }
+        somme_sin.atan2(somme_cos).rem_euclid(std::f64::consts::TAU) / echelle
+    };
+
+    (
+        moyenne(cases.iter().map(|coord| coord.x as f64).collect(), LARGEUR_CARTE),
+        moyenne(cases.iter().map(|coord| coord.y as f64).collect(), HAUTEUR_CARTE),
+    )
+}
+
+/// Case vide la plus proche d'un point, hors des zones sûres des stations déjà placées
+/// Si une composante est donnée, seules ses cases sont candidates
+fn case_vide_la_plus_proche(
+    carte: &Carte,
+    cible: (f64, f64),
+    composante: Option<usize>,
+    stations: &[Coord],
+    rayon_zone_sure: usize,
+) -> Option<Coord> {
+    let composantes = carte.composantes_connexes();
+
+    carte
+        .cases()
+        .filter(|&(_, type_pixel)| type_pixel == TypePixel::Vide)
+        .map(|(coord, _)| coord)
+        .filter(|&coord| composante.is_none() || composantes.composante(coord) == composante)
+        .filter(|&coord| stations.iter().all(|&station| carte.distance(coord, station) > rayon_zone_sure))
+        .min_by(|a, b| {
+            // Sur une carte torique, l'écart passe par le bord le plus proche
+            let ecart = |valeur: i32, cible: f64, taille: usize| {
+                let ecart = (valeur as f64 - cible).abs();
+                if carte.torique { ecart.min(taille as f64 - ecart) } else { ecart }
+            };
+            let distance = |coord: &Coord| {
+                ecart(coord.x, cible.0, LARGEUR_CARTE).powi(2) + ecart(coord.y, cible.1, HAUTEUR_CARTE).powi(2)
+            };
+            distance(a).total_cmp(&distance(b))
+        })
+}
+
+/// Place une station sur une case vide de la map et dégage une zone sûre autour d'elle
+/// Les ressources présentes dans la zone sont replacées ailleurs sur la carte
+fn placer_station(
+    carte: &mut Carte,
+    generateur_aleatoire: &mut StdRng,
+    parametres: &ParametresGeneration,
+    stations: &[Coord],
+) -> Coord {
+    let rayon_zone_sure = parametres.rayon_zone_sure;
+    let station = choisir_case_station(carte, generateur_aleatoire, parametres, stations);
+    carte.set(station, TypePixel::Station);
+
+    // Dégagement des obstacles et zones dangereuses autour de la station
+    let mut ressources_deplacees = Vec::new();
+    let rayon = rayon_zone_sure as i32;
+    for dy in -rayon..=rayon {
+        for dx in -rayon..=rayon {
+            let coord = Coord::new(station.x + dx, station.y + dy);
+
+            match carte.get(coord) {
+                Some(ressource @ (TypePixel::Energie | TypePixel::Minerai | TypePixel::SiteScientifique)) => {
+                    ressources_deplacees.push(ressource);
+                    carte.set(coord, TypePixel::Vide);
+                }
+                Some(TypePixel::Obstacle | TypePixel::ZoneDanger) => carte.set(coord, TypePixel::Vide),
+                _ => {}
+            }
+        }
+    }
+
+    // Les ressources retirées sont replacées sur des cases vides hors de la zone sûre
+    for ressource in ressources_deplacees {
+        loop {
+            let coord = case_aleatoire(generateur_aleatoire);
+
+            if carte.get(coord) == Some(TypePixel::Vide) && carte.distance(coord, station) > rayon_zone_sure {
+                carte.set(coord, ressource);
+                break;
+            }
+        }
+    }
+
+    station
+}
+
+/// Fonction limitant la taille des obstacles pour éviter des regroupements trop larges
+fn limiter_taille_obstacles(carte: &mut Carte) {
+    for coord in carte.coordonnees() {
+        if carte.get(coord) != Some(TypePixel::Obstacle) {
+            continue;
+        }
+
+        let mut taille_obstacle = 1;
+
+        for (dx, dy) in DIRECTIONS {
+            let mut courant = coord;
+            // Sur une carte torique, le parcours s'arrête avant de revenir à son point de départ
+            let longueur_max = if dx != 0 { LARGEUR_CARTE } else { HAUTEUR_CARTE };
+
+            for _ in 1..longueur_max {
+                let suivant = Coord::new(courant.x + dx, courant.y + dy);
+                if carte.get(suivant) != Some(TypePixel::Obstacle) {
+                    break;
+                }
+
+                taille_obstacle += 1;
+                if taille_obstacle > MAX_TAILLE_OBSTACLE {
+                    carte.set(suivant, TypePixel::Vide);
+                }
+
+                courant = suivant;
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ligne de la carte écrite avec un caractère par case, pour comparer facilement des cartes
+    fn ligne(carte: &Carte, y: i32) -> String {
+        (0..LARGEUR_CARTE as i32)
+            .map(|x| match carte.get(Coord::new(x, y)) {
+                Some(TypePixel::Vide) => '.',
+                Some(TypePixel::Obstacle) => '#',
+                Some(TypePixel::Energie) => 'E',
+                Some(TypePixel::Minerai) => 'M',
+                Some(TypePixel::SiteScientifique) => 'S',
+                Some(TypePixel::Station) => 'X',
+                Some(TypePixel::ZoneDanger) => '~',
+                None => '?',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn normaliser_replie_seulement_une_carte_torique() {
+        let bornee = Carte::new(false);
+        let torique = Carte::new(true);
+        let (largeur, hauteur) = (LARGEUR_CARTE as i32, HAUTEUR_CARTE as i32);
+
+        assert_eq!(bornee.normaliser(Coord::new(3, 4)), Some(Coord::new(3, 4)));
+        assert_eq!(bornee.normaliser(Coord::new(-1, 0)), None);
+        assert_eq!(bornee.normaliser(Coord::new(0, hauteur)), None);
+
+        assert_eq!(torique.normaliser(Coord::new(-1, 0)), Some(Coord::new(largeur - 1, 0)));
+        assert_eq!(torique.normaliser(Coord::new(largeur, hauteur)), Some(Coord::new(0, 0)));
+        assert_eq!(torique.normaliser(Coord::new(-largeur - 2, 2 * hauteur + 1)), Some(Coord::new(largeur - 2, 1)));
+    }
+
+    #[test]
+    fn get_et_set_hors_de_la_carte() {
+        let mut bornee = Carte::new(false);
+        bornee.set(Coord::new(-1, 0), TypePixel::Obstacle);
+        assert_eq!(bornee.get(Coord::new(-1, 0)), None);
+        assert!(bornee.cases().all(|(_, type_pixel)| type_pixel == TypePixel::Vide));
+
+        bornee.set(Coord::new(2, 3), TypePixel::Minerai);
+        assert_eq!(bornee.get(Coord::new(2, 3)), Some(TypePixel::Minerai));
+
+        // Sur une carte torique, la case modifiée est celle du bord opposé
+        let mut torique = Carte::new(true);
+        torique.set(Coord::new(-1, -1), TypePixel::Energie);
+        let coin = Coord::new(LARGEUR_CARTE as i32 - 1, HAUTEUR_CARTE as i32 - 1);
+        assert_eq!(torique.get(coin), Some(TypePixel::Energie));
+        assert_eq!(torique.get(Coord::new(-1, -1)), Some(TypePixel::Energie));
+    }
+
+    #[test]
+    fn voisins_d_un_coin() {
+        let (largeur, hauteur) = (LARGEUR_CARTE as i32, HAUTEUR_CARTE as i32);
+
+        let mut voisins: Vec<Coord> = Carte::new(false).voisins(Coord::new(0, 0)).collect();
+        voisins.sort_by_key(|coord| (coord.x, coord.y));
+        assert_eq!(voisins, vec![Coord::new(0, 1), Coord::new(1, 0)]);
+
+        let mut voisins: Vec<Coord> = Carte::new(true).voisins(Coord::new(0, 0)).collect();
+        voisins.sort_by_key(|coord| (coord.x, coord.y));
+        assert_eq!(
+            voisins,
+            vec![Coord::new(0, 1), Coord::new(0, hauteur - 1), Coord::new(1, 0), Coord::new(largeur - 1, 0)]
+        );
+    }
+
+    #[test]
+    fn distance_par_les_bords() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(LARGEUR_CARTE as i32 - 1, 4);
+
+        assert_eq!(Carte::new(false).distance(a, b), LARGEUR_CARTE - 2);
+        assert_eq!(Carte::new(true).distance(a, b), 2);
+        assert_eq!(Carte::new(true).distance(a, a), 0);
+    }
+
+    /// Carte coupée par un mur vertical en x = 2, avec un obstacle isolé en (5, 5)
+    fn carte_coupee(torique: bool) -> Carte {
+        let mut carte = Carte::new(torique);
+        for y in 0..HAUTEUR_CARTE as i32 {
+            carte.set(Coord::new(2, y), TypePixel::Obstacle);
+        }
+        carte.set(Coord::new(5, 5), TypePixel::Obstacle);
+        carte
+    }
+
+    #[test]
+    fn composantes_d_une_carte_coupee() {
+        let carte = carte_coupee(false);
+        let composantes = carte.composantes_connexes();
+
+        let gauche = composantes.composante(Coord::new(0, 0)).unwrap();
+        let droite = composantes.composante(Coord::new(3, 0)).unwrap();
+        assert_ne!(gauche, droite);
+        assert_eq!(composantes.taille(gauche), 2 * HAUTEUR_CARTE);
+        assert_eq!(composantes.taille(droite), (LARGEUR_CARTE - 3) * HAUTEUR_CARTE - 1);
+        assert_eq!(composantes.plus_grande(), Some(droite));
+
+        // Obstacles et cases hors carte n'appartiennent à aucune composante
+        assert_eq!(composantes.composante(Coord::new(2, 0)), None);
+        assert_eq!(composantes.composante(Coord::new(5, 5)), None);
+        assert_eq!(composantes.composante(Coord::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn composantes_recalculees_apres_set() {
+        let mut carte = Carte::new(false);
+        assert_eq!(carte.composantes_connexes().taille(0), LARGEUR_CARTE * HAUTEUR_CARTE);
+
+        // Un mur horizontal sépare la carte en deux : le cache doit être invalidé
+        for x in 0..LARGEUR_CARTE as i32 {
+            carte.set(Coord::new(x, 10), TypePixel::Obstacle);
+        }
+        let composantes = carte.composantes_connexes();
+        let bas = composantes.composante(Coord::new(0, 0)).unwrap();
+        let haut = composantes.composante(Coord::new(0, 11)).unwrap();
+        assert_ne!(bas, haut);
+        assert_eq!(composantes.taille(bas), 10 * LARGEUR_CARTE);
+        assert_eq!(composantes.taille(haut), (HAUTEUR_CARTE - 11) * LARGEUR_CARTE);
+
+        // Une brèche dans le mur réunit les deux moitiés
+        carte.set(Coord::new(7, 10), TypePixel::Vide);
+        let composantes = carte.composantes_connexes();
+        let reunie = composantes.composante(Coord::new(0, 0)).unwrap();
+        assert_eq!(composantes.composante(Coord::new(0, 11)), Some(reunie));
+        assert_eq!(composantes.taille(reunie), (HAUTEUR_CARTE - 1) * LARGEUR_CARTE + 1);
+    }
+
+    #[test]
+    fn composantes_reliees_par_les_bords_d_une_carte_torique() {
+        // Le mur en x = 2 ne coupe plus la carte : on en fait le tour par le bord gauche
+        let carte = carte_coupee(true);
+        let composantes = carte.composantes_connexes();
+
+        let gauche = composantes.composante(Coord::new(0, 0)).unwrap();
+        assert_eq!(composantes.composante(Coord::new(3, 0)), Some(gauche));
+        assert_eq!(composantes.taille(gauche), LARGEUR_CARTE * HAUTEUR_CARTE - HAUTEUR_CARTE - 1);
+
+        // Les coordonnées hors carte sont repliées, comme avec Carte::get
+        assert_eq!(composantes.composante(Coord::new(-1, -1)), Some(gauche));
+        assert_eq!(composantes.composante(Coord::new(LARGEUR_CARTE as i32 + 2, 0)), None);
+        assert_eq!(composantes.composante(Coord::new(5, 5 + HAUTEUR_CARTE as i32)), None);
+    }
+
+    /// Choisit la case d'une station sur la carte avec la stratégie donnée
+    fn case_station(carte: &Carte, placement: PlacementStation, stations: &[Coord]) -> Coord {
+        let mut parametres = ParametresGeneration::depuis_difficulte(Difficulte::Normal);
+        parametres.placement_station = placement;
+        choisir_case_station(carte, &mut StdRng::seed_from_u64(0), &parametres, stations)
+    }
+
+    #[test]
+    fn station_au_centre() {
+        // A égalité de distance, la première case dans l'ordre des lignes l'emporte
+        let carte = Carte::new(false);
+        assert_eq!(case_station(&carte, PlacementStation::Centre, &[]), Coord::new(24, 14));
+    }
+
+    #[test]
+    fn station_dans_la_plus_grande_region() {
+        // Le mur en x = 20 laisse 20 colonnes à gauche et 29 à droite, centrées sur x = 35
+        let mut carte = Carte::new(false);
+        for y in 0..HAUTEUR_CARTE as i32 {
+            carte.set(Coord::new(20, y), TypePixel::Obstacle);
+        }
+
+        assert_eq!(case_station(&carte, PlacementStation::MeilleureConnectivite, &[]), Coord::new(35, 14));
+        assert_eq!(case_station(&carte, PlacementStation::Centre, &[]), Coord::new(24, 14));
+    }
+
+    #[test]
+    fn station_dans_une_region_a_cheval_sur_un_bord() {
+        // Les murs en x = 15 et x = 35 laissent 19 colonnes au milieu et 29 à cheval sur le bord, centrées sur x = 0
+        let mut carte = Carte::new(true);
+        for y in 0..HAUTEUR_CARTE as i32 {
+            carte.set(Coord::new(15, y), TypePixel::Obstacle);
+            carte.set(Coord::new(35, y), TypePixel::Obstacle);
+        }
+
+        assert_eq!(case_station(&carte, PlacementStation::MeilleureConnectivite, &[]), Coord::new(0, 14));
+    }
+
+    #[test]
+    fn stations_suivantes_hors_des_zones_sures() {
+        let carte = Carte::new(false);
+        let premiere = Coord::new(24, 14);
+
+        let seconde = case_station(&carte, PlacementStation::Centre, &[premiere]);
+        assert!(carte.distance(premiere, seconde) > RAYON_ZONE_SURE);
+        // Le centre (24.5, 14.5) n'est pas symétrique par rapport à la première station : la droite est plus proche
+        assert_eq!(seconde, Coord::new(27, 14));
+    }
+
+    #[test]
+    fn station_aleatoire_si_aucune_case_ne_convient() {
+        // Seule case vide : dans la zone sûre d'une station déjà placée
+        let mut carte = Carte::new(false);
+        for coord in carte.coordonnees() {
+            carte.set(coord, TypePixel::Obstacle);
+        }
+        carte.set(Coord::new(0, 0), TypePixel::Vide);
+        let stations = [Coord::new(1, 1)];
+
+        assert_eq!(case_vide_la_plus_proche(&carte, (0.0, 0.0), None, &stations, RAYON_ZONE_SURE), None);
+        for placement in [PlacementStation::Centre, PlacementStation::MeilleureConnectivite] {
+            assert_eq!(case_station(&carte, placement, &stations), Coord::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn zones_sures_degagees_et_ressources_conservees() {
+        let ressources = |carte: &Carte| -> Vec<usize> {
+            [TypePixel::Energie, TypePixel::Minerai, TypePixel::SiteScientifique]
+                .iter()
+                .map(|&attendu| carte.cases().filter(|&(_, type_pixel)| type_pixel == attendu).count())
+                .collect()
+        };
+
+        for torique in [false, true] {
+            let mut parametres = ParametresGeneration::depuis_difficulte(Difficulte::Difficile);
+            parametres.nb_stations = 4;
+            parametres.rayon_zone_sure = 3;
+            parametres.torique = torique;
+            let carte = generer_carte(2024, &parametres);
+
+            let stations: Vec<Coord> = carte
+                .cases()
+                .filter(|&(_, type_pixel)| type_pixel == TypePixel::Station)
+                .map(|(coord, _)| coord)
+                .collect();
+            assert_eq!(stations.len(), 4);
+            for (coord, type_pixel) in carte.cases() {
+                if matches!(type_pixel, TypePixel::Obstacle | TypePixel::ZoneDanger) {
+                    assert!(
+                        stations.iter().all(|&station| carte.distance(coord, station) > 3),
+                        "{:?} en {:?} dans une zone sûre (torique : {})",
+                        type_pixel,
+                        coord,
+                        torique
+                    );
+                }
+            }
+
+            // Les ressources de la zone sont déplacées, pas supprimées, et toujours au même endroit
+            let relancee = generer_carte(2024, &parametres);
+            assert_eq!(carte.cases().collect::<Vec<_>>(), relancee.cases().collect::<Vec<_>>());
+            parametres.rayon_zone_sure = 0;
+            assert_eq!(ressources(&carte), ressources(&generer_carte(2024, &parametres)));
+        }
+    }
+
+    #[test]
+    fn carte_d_une_seed_connue() {
+        // Toute modification de la génération qui change les cartes existantes fait échouer ce test
+        let carte = generer_carte(123456789, &ParametresGeneration::depuis_difficulte(Difficulte::Normal));
+
+        let nombres: Vec<usize> = TypePixel::TOUS
+            .iter()
+            .map(|&attendu| carte.cases().filter(|&(_, type_pixel)| type_pixel == attendu).count())
+            .collect();
+        assert_eq!(nombres, vec![1125, 69, 85, 61, 49, 1, 110]);
+        assert_eq!(carte.get(Coord::new(18, 1)), Some(TypePixel::Station));
+
+        assert_eq!(ligne(&carte, 0), ".......S...............SM.M..M.E..................");
+        assert_eq!(ligne(&carte, 15), "###..M.S..S....~~~~..........E.M.....#..M~~~...###");
+        assert_eq!(ligne(&carte, 29), ".......M......###....S....S.......E....~E..EE###.E");
+    }
+}
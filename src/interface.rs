@@ -0,0 +1,450 @@
+use crate::affichage::TAILLE_CASE;
+use crate::camera::{case_sous_curseur, rectangle_carte};
+use crate::carte::{Carte, Coord, TypePixel, HAUTEUR_CARTE, LARGEUR_CARTE};
+use crate::langue::tr;
+use crate::simulation::{
+    EntitePartie, EtatApplication, HorlogeSimulation, PhaseJournee, SeedCarte, TickSimulation, VitesseSimulation,
+};
+use crate::theme::{ThemeCouleurs, TileTheme};
+use bevy::diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use bevy::window::PrimaryWindow;
+
+// Taille du texte de l'interface en pixels
+const TAILLE_TEXTE: f32 = 18.0;
+
+// Taille d'une case de la minimap en pixels
+const ECHELLE_MINIMAP: f32 = 4.0;
+
+// Marge entre la minimap et le bord de la fenêtre
+const MARGE_MINIMAP: f32 = 10.0;
+
+// Taille de l'échantillon de couleur devant chaque ligne de la légende
+const TAILLE_ECHANTILLON: f32 = 16.0;
+
+// Décalage de l'info-bulle par rapport au curseur, pour ne pas le masquer
+const DECALAGE_INFO_BULLE: f32 = 16.0;
+
+/// Composant Bevy marquant le texte de la barre d'informations
+#[derive(Component)]
+pub struct TexteHud;
+
+/// Composant Bevy marquant l'image de la minimap
+#[derive(Component)]
+pub struct Minimap;
+
+/// Composant Bevy marquant le rectangle de la minimap qui montre la zone visible par la caméra
+#[derive(Component)]
+pub struct CadreVue;
+
+/// Composant Bevy marquant l'info-bulle qui décrit la case survolée
+#[derive(Component)]
+pub struct InfoBulle;
+
+/// Composant Bevy marquant le panneau de légende
+#[derive(Component)]
+pub struct Legende;
+
+/// Composant Bevy marquant le texte de l'affichage de débogage
+#[derive(Component)]
+pub struct TexteDebogage;
+
+/// Crée la barre d'informations affichée en haut de la fenêtre
+pub fn creer_hud(mut commandes: Commands, couleurs: Res<ThemeCouleurs>) {
+    commandes
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                    ..Default::default()
+                },
+                background_color: couleurs.interface.fond_panneau.into(),
+                ..Default::default()
+            },
+            EntitePartie,
+        ))
+        .with_children(|barre| {
+            barre.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: TAILLE_TEXTE,
+                        color: couleurs.interface.texte,
+                        ..Default::default()
+                    },
+                ),
+                TexteHud,
+            ));
+        });
+}
+
+/// Met à jour la barre d'informations avec la seed, le tick, l'état du cycle jour/nuit et la vitesse (ou la pause)
+pub fn mettre_a_jour_hud(
+    seed_carte: Res<SeedCarte>,
+    tick: Res<TickSimulation>,
+    horloge: Res<HorlogeSimulation>,
+    vitesse: Res<VitesseSimulation>,
+    etat: Res<State<EtatApplication>>,
+    mut textes: Query<&mut Text, With<TexteHud>>,
+) {
+    let phase = match horloge.phase {
+        PhaseJournee::Jour => tr("hud.jour"),
+        PhaseJournee::Nuit => tr("hud.nuit"),
+    };
+    let restant = horloge.minuterie.remaining_secs().ceil();
+    let etat_vitesse = if *etat.get() == EtatApplication::Pause {
+        tr("hud.en_pause").to_string()
+    } else {
+        format!("{} x{}", tr("hud.vitesse"), vitesse.multiplicateur)
+    };
+
+    for mut texte in textes.iter_mut() {
+        texte.sections[0].value = format!(
+            "{}{}{}  |  {} {}  |  {} ({} s {})  |  {}",
+            tr("hud.seed"),
+            tr("deux_points"),
+            seed_carte.seed,
+            tr("hud.tick"),
+            tick.0,
+            phase,
+            restant,
+            tr("hud.restantes"),
+            etat_vitesse
+        );
+    }
+}
+
+/// Crée l'info-bulle, masquée tant que la souris ne survole pas la carte
+pub fn creer_info_bulle(mut commandes: Commands, couleurs: Res<ThemeCouleurs>) {
+    commandes.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                padding: UiRect::all(Val::Px(4.0)),
+                display: Display::None,
+                ..Default::default()
+            },
+            background_color: couleurs.interface.fond_info_bulle.into(),
+            z_index: ZIndex::Global(1),
+            ..TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: TAILLE_TEXTE,
+                    color: couleurs.interface.texte,
+                    ..Default::default()
+                },
+            )
+        },
+        InfoBulle,
+        EntitePartie,
+    ));
+}
+
+/// Affiche les coordonnées et le type de la case survolée à côté du curseur
+pub fn mettre_a_jour_info_bulle(
+    carte: Res<Carte>,
+    fenetres: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut info_bulles: Query<(&mut Style, &mut Text), With<InfoBulle>>,
+) {
+    let (Ok(fenetre), Ok((camera, transform_camera))) = (fenetres.get_single(), cameras.get_single()) else {
+        return;
+    };
+    let survol = case_sous_curseur(fenetre, camera, transform_camera)
+        .and_then(|case| Some((case, carte.get(case)?, fenetre.cursor_position()?)));
+
+    for (mut style, mut texte) in info_bulles.iter_mut() {
+        let Some((case, type_pixel, curseur)) = survol else {
+            style.display = Display::None;
+            continue;
+        };
+        style.display = Display::Flex;
+        style.left = Val::Px(curseur.x + DECALAGE_INFO_BULLE);
+        style.top = Val::Px(curseur.y + DECALAGE_INFO_BULLE);
+        texte.sections[0].value = format!("({}, {})\n{}", case.x, case.y, libelle_type_pixel(type_pixel));
+    }
+}
+
+/// Crée le panneau de légende, sous la barre d'informations à droite
+/// Chaque type de case est présenté avec sa texture si le thème en fournit une, sa couleur sinon
+pub fn creer_legende(mut commandes: Commands, theme: Res<TileTheme>, couleurs: Res<ThemeCouleurs>) {
+    commandes
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(MARGE_MINIMAP),
+                    top: Val::Px(40.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: couleurs.interface.fond_panneau.into(),
+                ..Default::default()
+            },
+            Legende,
+            EntitePartie,
+        ))
+        .with_children(|panneau| {
+            for type_pixel in TypePixel::TOUS {
+                panneau
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(8.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|ligne| {
+                        let taille = Style {
+                            width: Val::Px(TAILLE_ECHANTILLON),
+                            height: Val::Px(TAILLE_ECHANTILLON),
+                            ..Default::default()
+                        };
+                        match theme.textures.get(&type_pixel) {
+                            Some(texture) => ligne.spawn(ImageBundle {
+                                style: taille,
+                                image: UiImage::new(texture.clone()),
+                                ..Default::default()
+                            }),
+                            None => ligne.spawn(NodeBundle {
+                                style: taille,
+                                background_color: couleurs.couleur(type_pixel).into(),
+                                ..Default::default()
+                            }),
+                        };
+                        ligne.spawn(TextBundle::from_section(
+                            libelle_type_pixel(type_pixel),
+                            TextStyle {
+                                font_size: TAILLE_TEXTE,
+                                color: couleurs.interface.texte,
+                                ..Default::default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+/// Affiche ou masque la légende avec la touche L
+pub fn basculer_legende(clavier: Res<Input<KeyCode>>, mut legendes: Query<&mut Visibility, With<Legende>>) {
+    if !clavier.just_pressed(KeyCode::L) {
+        return;
+    }
+
+    for mut visibilite in legendes.iter_mut() {
+        *visibilite = match *visibilite {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// Nom d'un type de pixel tel qu'affiché dans l'interface, dans la langue choisie
+fn libelle_type_pixel(type_pixel: TypePixel) -> &'static str {
+    match type_pixel {
+        TypePixel::Vide => tr("case.vide"),
+        TypePixel::Obstacle => tr("case.obstacle"),
+        TypePixel::Energie => tr("case.energie"),
+        TypePixel::Minerai => tr("case.minerai"),
+        TypePixel::SiteScientifique => tr("case.site_scientifique"),
+        TypePixel::Station => tr("case.station"),
+        TypePixel::ZoneDanger => tr("case.zone_danger"),
+    }
+}
+
+/// Crée la minimap dans le coin inférieur droit de la fenêtre
+/// Son image est remplie par `mettre_a_jour_minimap` dès que la carte est disponible
+pub fn creer_minimap(mut commandes: Commands, mut images: ResMut<Assets<Image>>, couleurs: Res<ThemeCouleurs>) {
+    let image = images.add(Image::default());
+
+    commandes
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(MARGE_MINIMAP),
+                    bottom: Val::Px(MARGE_MINIMAP),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..Default::default()
+                },
+                border_color: couleurs.interface.bordure_minimap.into(),
+                ..Default::default()
+            },
+            EntitePartie,
+        ))
+        .with_children(|cadre| {
+            cadre.spawn((
+                ImageBundle {
+                    style: Style {
+                        width: Val::Px(LARGEUR_CARTE as f32 * ECHELLE_MINIMAP),
+                        height: Val::Px(HAUTEUR_CARTE as f32 * ECHELLE_MINIMAP),
+                        ..Default::default()
+                    },
+                    image: UiImage::new(image),
+                    ..Default::default()
+                },
+                Minimap,
+            ));
+            cadre.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..Default::default()
+                    },
+                    border_color: couleurs.interface.cadre_vue.into(),
+                    ..Default::default()
+                },
+                CadreVue,
+            ));
+        });
+}
+
+/// Redessine la minimap à chaque modification de la carte
+pub fn mettre_a_jour_minimap(
+    carte: Res<Carte>,
+    couleurs: Res<ThemeCouleurs>,
+    mut images: ResMut<Assets<Image>>,
+    minimaps: Query<&UiImage, With<Minimap>>,
+) {
+    if !carte.is_changed() {
+        return;
+    }
+
+    for minimap in minimaps.iter() {
+        if let Some(image) = images.get_mut(&minimap.texture) {
+            *image = image_carte(&carte, &couleurs);
+        }
+    }
+}
+
+/// Place le rectangle de la minimap sur la partie de la carte visible par la caméra
+pub fn mettre_a_jour_cadre_vue(
+    cameras: Query<(&Transform, &OrthographicProjection), With<Camera2d>>,
+    mut cadres: Query<&mut Style, With<CadreVue>>,
+) {
+    let Ok((transform, projection)) = cameras.get_single() else {
+        return;
+    };
+    let carte = rectangle_carte();
+    let centre = transform.translation.truncate();
+    let vue = Rect::from_corners(centre + projection.area.min, centre + projection.area.max).intersect(carte);
+
+    // Conversion des unités du monde en pixels de la minimap (l'axe y de l'interface est orienté vers le bas)
+    let echelle = ECHELLE_MINIMAP / TAILLE_CASE;
+
+    for mut style in cadres.iter_mut() {
+        if vue.is_empty() {
+            style.display = Display::None;
+            continue;
+        }
+        style.display = Display::Flex;
+        style.left = Val::Px((vue.min.x - carte.min.x) * echelle);
+        style.top = Val::Px((carte.max.y - vue.max.y) * echelle);
+        style.width = Val::Px(vue.width() * echelle);
+        style.height = Val::Px(vue.height() * echelle);
+    }
+}
+
+/// Image de la carte avec un pixel par case, aux couleurs du thème
+fn image_carte(carte: &Carte, couleurs: &ThemeCouleurs) -> Image {
+    let mut donnees = Vec::with_capacity(LARGEUR_CARTE * HAUTEUR_CARTE * 4);
+
+    // La première ligne de l'image est en haut, alors que y = 0 est en bas de la carte
+    for y in (0..HAUTEUR_CARTE as i32).rev() {
+        for x in 0..LARGEUR_CARTE as i32 {
+            let type_pixel = carte.get(Coord::new(x, y)).unwrap_or(TypePixel::Vide);
+            donnees.extend_from_slice(&couleurs.couleur(type_pixel).as_rgba_u8());
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: LARGEUR_CARTE as u32,
+            height: HAUTEUR_CARTE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        donnees,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    // Pas de lissage : chaque case reste un carré net une fois agrandie
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+/// Crée l'affichage de débogage (FPS, nombre d'entités), masqué jusqu'à l'appui sur F3
+pub fn creer_debogage(mut commandes: Commands, couleurs: Res<ThemeCouleurs>) {
+    commandes.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(MARGE_MINIMAP),
+                bottom: Val::Px(MARGE_MINIMAP),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..Default::default()
+            },
+            background_color: couleurs.interface.fond_panneau.into(),
+            visibility: Visibility::Hidden,
+            // Au-dessus de tous les écrans, menus compris
+            z_index: ZIndex::Global(3),
+            ..TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: TAILLE_TEXTE,
+                    color: couleurs.interface.texte,
+                    ..Default::default()
+                },
+            )
+        },
+        TexteDebogage,
+    ));
+}
+
+/// Affiche ou masque l'affichage de débogage avec la touche F3
+pub fn basculer_debogage(clavier: Res<Input<KeyCode>>, mut textes: Query<&mut Visibility, With<TexteDebogage>>) {
+    if !clavier.just_pressed(KeyCode::F3) {
+        return;
+    }
+
+    for mut visibilite in textes.iter_mut() {
+        *visibilite = match *visibilite {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// Met à jour l'affichage de débogage à partir des diagnostics de Bevy
+pub fn mettre_a_jour_debogage(
+    diagnostics: Res<DiagnosticsStore>,
+    mut textes: Query<(&mut Text, &Visibility), With<TexteDebogage>>,
+) {
+    let valeur = |id| {
+        diagnostics
+            .get(id)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or(0.0)
+    };
+
+    for (mut texte, visibilite) in textes.iter_mut() {
+        if *visibilite == Visibility::Hidden {
+            continue;
+        }
+        texte.sections[0].value = format!(
+            "FPS{}{:.0}  ({:.1} ms)\n{}{}{:.0}",
+            tr("deux_points"),
+            valeur(FrameTimeDiagnosticsPlugin::FPS),
+            valeur(FrameTimeDiagnosticsPlugin::FRAME_TIME),
+            tr("debogage.entites"),
+            tr("deux_points"),
+            valeur(EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+        );
+    }
+}
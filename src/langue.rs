@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Enumération des langues de l'interface et de la console
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Langue {
+    Francais,
+    Anglais,
+}
+
+impl Langue {
+    /// Retourne la langue correspondant à son code (fr, en)
+    pub fn depuis_nom(nom: &str) -> Option<Langue> {
+        match nom {
+            "fr" => Some(Langue::Francais),
+            "en" => Some(Langue::Anglais),
+            _ => None,
+        }
+    }
+}
+
+// Textes affichés, sous la forme (clé, français, anglais)
+const TEXTES: &[(&str, &str, &str)] = &[
+    // Ponctuation
+    ("deux_points", " : ", ": "),
+    // Console
+    ("log.seed", "Seed utilisée", "Seed used"),
+    ("log.carte_chargee", "Carte chargée", "Map loaded"),
+    ("log.chargement_impossible", "Impossible de charger la carte", "Could not load the map"),
+    ("log.carte_sauvegardee", "Carte sauvegardée", "Map saved"),
+    ("log.sauvegarde_impossible", "Impossible de sauvegarder la carte", "Could not save the map"),
+    ("log.autosave", "Sauvegarde automatique", "Autosave"),
+    (
+        "log.autosave_suppression",
+        "Impossible de supprimer une ancienne sauvegarde automatique",
+        "Could not delete an old autosave",
+    ),
+    (
+        "log.dossier_autosave",
+        "Impossible de créer le dossier des sauvegardes automatiques",
+        "Could not create the autosave folder",
+    ),
+    (
+        "log.dossier_enregistrement",
+        "Impossible de créer le dossier d'enregistrement",
+        "Could not create the recording folder",
+    ),
+    (
+        "log.niveau_inconnu",
+        "Niveau de journalisation inconnu, attendu error, warn, info, debug ou trace",
+        "Unknown log level, expected error, warn, info, debug or trace",
+    ),
+    ("log.langue_inconnue", "Langue inconnue, attendu fr ou en", "Unknown language, expected fr or en"),
+    ("log.valeur_invalide", "Valeur invalide, valeur par défaut utilisée", "Invalid value, using the default"),
+    (
+        "log.difficulte_inconnue",
+        "Difficulté inconnue, attendu facile, normal ou difficile",
+        "Unknown difficulty, expected facile, normal or difficile",
+    ),
+    ("log.difficulte", "Difficulté", "Difficulty"),
+    (
+        "log.placement_inconnu",
+        "Placement de station inconnu, attendu aleatoire, centre ou meilleure-connectivite",
+        "Unknown station placement, expected aleatoire, centre or meilleure-connectivite",
+    ),
+    (
+        "log.textures_absentes",
+        "Aucune texture trouvée, affichage en couleurs unies",
+        "No texture found, using solid colours",
+    ),
+    (
+        "log.theme_impossible",
+        "Impossible de lire le thème de couleurs, couleurs par défaut utilisées",
+        "Could not read the colour theme, using the default colours",
+    ),
+    ("log.pause", "Pause", "Pause"),
+    ("log.vitesse", "Vitesse de la simulation", "Simulation speed"),
+    ("log.pas_a_pas", "Avance d'un tick", "Stepped one tick"),
+    ("log.changement_phase", "Changement de phase", "Phase change"),
+    ("log.station_placee", "Station placée", "Station placed"),
+    ("log.region_accessible", "Région accessible depuis la station", "Region reachable from the station"),
+    (
+        "erreur.dimensions",
+        "les dimensions de la carte ne correspondent pas",
+        "the map dimensions do not match",
+    ),
+    (
+        "erreur.couleur_attendue",
+        "couleur attendue sous la forme \"#rrggbb\" ou \"#rrggbbaa\"",
+        "expected a colour written as \"#rrggbb\" or \"#rrggbbaa\"",
+    ),
+    ("erreur.case_inconnue", "case ou élément d'interface inconnu", "unknown tile or interface element"),
+    // Barre d'informations
+    ("hud.seed", "Seed", "Seed"),
+    ("hud.tick", "Tick", "Tick"),
+    ("hud.jour", "Jour", "Day"),
+    ("hud.nuit", "Nuit", "Night"),
+    ("hud.restantes", "restantes", "left"),
+    ("hud.en_pause", "En pause", "Paused"),
+    ("hud.vitesse", "Vitesse", "Speed"),
+    // Affichage de débogage
+    ("debogage.entites", "Entités", "Entities"),
+    // Types de case
+    ("case.vide", "Vide", "Empty"),
+    ("case.obstacle", "Obstacle", "Obstacle"),
+    ("case.energie", "Énergie", "Energy"),
+    ("case.minerai", "Minerai", "Ore"),
+    ("case.site_scientifique", "Site scientifique", "Science site"),
+    ("case.station", "Station", "Station"),
+    ("case.zone_danger", "Zone de danger", "Danger zone"),
+    // Menu
+    ("menu.titre", "Simulation de Robots", "Robot Simulation"),
+    ("menu.seed_aleatoire", "(aléatoire)", "(random)"),
+    ("menu.facile", "Facile", "Easy"),
+    ("menu.normal", "Normal", "Normal"),
+    ("menu.difficile", "Difficile", "Hard"),
+    ("menu.demarrer", "Démarrer (Entrée)", "Start (Enter)"),
+    // Fin de partie
+    ("resume.titre", "Fin de la partie", "End of the game"),
+    ("resume.difficulte", "Difficulté", "Difficulty"),
+    ("resume.duree", "Durée simulée", "Simulated time"),
+    ("resume.cycles", "cycles jour/nuit", "day/night cycles"),
+    ("resume.ticks", "Ticks écoulés", "Elapsed ticks"),
+    ("resume.rejouer", "Rejouer ce seed", "Replay this seed"),
+    ("resume.menu", "Menu", "Menu"),
+];
+
+// Table clé -> texte de la langue choisie, remplie une seule fois
+static TRADUCTIONS: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+/// Table des textes d'une langue
+fn table(langue: Langue) -> HashMap<&'static str, &'static str> {
+    TEXTES
+        .iter()
+        .map(|&(cle, francais, anglais)| match langue {
+            Langue::Francais => (cle, francais),
+            Langue::Anglais => (cle, anglais),
+        })
+        .collect()
+}
+
+/// Choisit la langue des textes ; sans effet si un texte a déjà été traduit
+pub fn choisir_langue(langue: Langue) {
+    let _ = TRADUCTIONS.set(table(langue));
+}
+
+/// Texte associé à la clé dans la langue choisie (français par défaut)
+/// Une clé inconnue est retournée telle quelle, pour être repérée facilement à l'écran
+pub fn tr(cle: &'static str) -> &'static str {
+    TRADUCTIONS
+        .get_or_init(|| table(Langue::Francais))
+        .get(cle)
+        .copied()
+        .unwrap_or(cle)
+}
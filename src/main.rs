@@ -1,57 +1,233 @@
+mod affichage;
+mod camera;
+mod carte;
+mod interface;
+mod langue;
+mod menu;
+mod sauvegarde;
+mod simulation;
+mod theme;
+
+use affichage::{EnregistrementImages, INTERVALLE_CAPTURE};
+use bevy::diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
-use noise::{NoiseFn, Perlin};
-use rand::{prelude::*, SeedableRng};
+use carte::{Difficulte, PlacementStation, ReglagesGeneration, MAX_RAYON_ZONE_SURE, MAX_STATIONS};
+use langue::{choisir_langue, tr, Langue};
+use menu::ConfigurationPartie;
+use sauvegarde::{FichierSauvegarde, SauvegardeAuto, AUTOSAVES_CONSERVEES, INTERVALLE_AUTOSAVE};
+use simulation::{EtatApplication, VitesseSimulation, TICKS_PAR_SECONDE};
 use std::env;
+use std::fs;
+use std::fmt::Display;
+use std::str::FromStr;
+use theme::{ThemeCouleurs, TileTheme};
+use tracing_subscriber::EnvFilter;
 // cargo run = génération aléatoire de la map
 // cargo run -- xxxxxx = Génération d'un seed x donné
 
-// Paramètres de la carte
-const LARGEUR_CARTE: usize = 50;
-const HAUTEUR_CARTE: usize = 30;
-const TAILLE_CASE: f32 = 20.0;
-
-// Seuil de bruit définissant les obstacles (plus haut = plus d'obstacles)
-const SEUIL_OBSTACLE: f64 = 0.5;
-
-// Taille maximale des obstacles en pixels connectés
-// Pour éviter d'avoir des obstacles trop grands.
-const MAX_TAILLE_OBSTACLE: usize = 5;
-
-/// Enumération des types de pixel présents sur la carte
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum TypePixel {
-    Vide,
-    Obstacle,
-    Energie,
-    Minerai,
-    SiteScientifique,
-    Station,
-}
+fn main() {
+    // La langue est choisie avant le premier message, pour que la console soit traduite elle aussi
+    let langue_inconnue = obtenir_valeur_option("--lang").filter(|nom| match Langue::depuis_nom(nom) {
+        Some(langue) => {
+            choisir_langue(langue);
+            false
+        }
+        None => true,
+    });
 
-/// Composant Bevy pour les entités représentant un pixel de la carte
-#[derive(Component)]
-struct Pixel {
-    type_pixel: TypePixel,
-}
+    initialiser_journalisation();
+    if let Some(nom) = langue_inconnue {
+        warn!(%nom, "{}", tr("log.langue_inconnue"));
+    }
 
-fn main() {
-    // Vérifie si l'utilisateur a fourni une seed en argument ou en génère une aléatoire
-    let seed = obtenir_seed_depuis_arguments().unwrap_or_else(generer_seed_aleatoire);
-    println!("Seed utilisée : {}", seed);
-
-    // Initialisation de Bevy avec la seed stockée
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .insert_resource(SeedCarte { seed }) // Stocke la seed pour garantir une génération reproductible
-        .add_systems(Startup, initialiser_map)
-        .add_systems(Startup, generer_map)
-        .run();
+    // Une carte sauvegardée remplace la génération (et fournit sa propre seed)
+    let sauvegarde_chargee = obtenir_valeur_option("--charger").and_then(|chemin| match sauvegarde::charger(&chemin) {
+        Ok(sauvegarde) => {
+            info!(fichier = %chemin, "{}", tr("log.carte_chargee"));
+            Some(sauvegarde)
+        }
+        Err(erreur) => {
+            error!(fichier = %chemin, %erreur, "{}", tr("log.chargement_impossible"));
+            None
+        }
+    });
+
+    // La seed fournie en argument (ou celle de la sauvegarde) pré-remplit le menu
+    let saisie_seed = match &sauvegarde_chargee {
+        Some(sauvegarde) => Some(sauvegarde.seed),
+        None => obtenir_seed_depuis_arguments(),
+    }
+    .map(|seed| seed.to_string())
+    .unwrap_or_default();
+
+    let dossier_tuiles = obtenir_valeur_option("--theme-tuiles").unwrap_or_else(|| "tiles".to_string());
+    let couleurs = lire_theme_couleurs();
+
+    let mut app = App::new();
+    // Les journaux sont gérés par l'abonné installé dans initialiser_journalisation
+    app.add_plugins(DefaultPlugins.build().disable::<LogPlugin>())
+        .add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin))
+        .add_state::<EtatApplication>()
+        .insert_resource(ConfigurationPartie {
+            saisie_seed,
+            difficulte: lire_difficulte(),
+            reglages: lire_reglages_generation(),
+            sauvegarde: sauvegarde_chargee,
+        })
+        .insert_resource(TileTheme::new(dossier_tuiles))
+        .insert_resource(ClearColor(couleurs.fond))
+        .insert_resource(couleurs)
+        .insert_resource(VitesseSimulation { multiplicateur: 1.0 })
+        .insert_resource(Time::<Fixed>::from_hz(TICKS_PAR_SECONDE))
+        .add_systems(Startup, (camera::creer_camera, theme::charger_tile_theme, interface::creer_debogage))
+        .add_systems(Update, (interface::basculer_debogage, interface::mettre_a_jour_debogage).chain())
+        .add_systems(OnEnter(EtatApplication::Menu), menu::creer_menu)
+        .add_systems(
+            Update,
+            (
+                menu::saisir_seed,
+                menu::demarrer_avec_entree,
+            )
+                .run_if(in_state(EtatApplication::Menu)),
+        )
+        .add_systems(
+            Update,
+            (menu::cliquer_boutons, menu::colorer_boutons)
+                .run_if(in_state(EtatApplication::Menu).or_else(in_state(EtatApplication::FinDePartie))),
+        )
+        .add_systems(OnExit(EtatApplication::Menu), menu::supprimer_menu)
+        .add_systems(OnEnter(EtatApplication::FinDePartie), menu::creer_resume)
+        .add_systems(OnExit(EtatApplication::FinDePartie), menu::supprimer_menu)
+        .add_systems(
+            OnTransition {
+                from: EtatApplication::Menu,
+                to: EtatApplication::EnCours,
+            },
+            (
+                simulation::demarrer_partie,
+                apply_deferred,
+                affichage::generer_map,
+                interface::creer_hud,
+                interface::creer_minimap,
+                interface::creer_info_bulle,
+                interface::creer_legende,
+            )
+                .chain(),
+        )
+        .add_systems(
+            OnTransition {
+                from: EtatApplication::EnCours,
+                to: EtatApplication::Menu,
+            },
+            simulation::nettoyer_partie,
+        )
+        .add_systems(
+            OnTransition {
+                from: EtatApplication::Pause,
+                to: EtatApplication::Menu,
+            },
+            simulation::nettoyer_partie,
+        )
+        .add_systems(
+            OnTransition {
+                from: EtatApplication::FinDePartie,
+                to: EtatApplication::Menu,
+            },
+            simulation::nettoyer_partie,
+        )
+        .add_systems(
+            FixedUpdate,
+            (simulation::avancer_simulation, sauvegarde::sauvegarder_automatiquement)
+                .chain()
+                .run_if(in_state(EtatApplication::EnCours)),
+        )
+        .add_systems(
+            Update,
+            // Aussi en pause, où la touche . peut faire changer de phase
+            affichage::appliquer_lumiere_ambiante.run_if(simulation::partie_affichee),
+        )
+        .add_systems(
+            Update,
+            (
+                simulation::controler_vitesse,
+                simulation::retourner_au_menu,
+                simulation::terminer_partie,
+                interface::mettre_a_jour_hud,
+                interface::mettre_a_jour_minimap,
+                interface::basculer_legende,
+                (
+                    (camera::deplacer_camera_clavier, camera::deplacer_camera_souris, camera::zoomer_camera),
+                    camera::limiter_camera,
+                    (interface::mettre_a_jour_cadre_vue, interface::mettre_a_jour_info_bulle),
+                )
+                    .chain(),
+            )
+                .run_if(simulation::partie_affichee),
+        )
+        .add_systems(
+            Update,
+            affichage::capturer_image
+                .after(affichage::appliquer_lumiere_ambiante)
+                .run_if(in_state(EtatApplication::EnCours)),
+        )
+        .add_systems(Last, sauvegarde::sauvegarder_a_la_sortie);
+
+    if let Some(chemin) = obtenir_valeur_option("--sauvegarde") {
+        app.insert_resource(FichierSauvegarde { chemin });
+    }
+    if let Some(dossier) = obtenir_valeur_option("--autosave") {
+        match fs::create_dir_all(&dossier) {
+            Ok(()) => {
+                let intervalle =
+                    lire_option_numerique("--autosave-intervalle", 1, u64::MAX).unwrap_or(INTERVALLE_AUTOSAVE);
+                let conservees =
+                    lire_option_numerique("--autosave-conserver", 1, usize::MAX).unwrap_or(AUTOSAVES_CONSERVEES);
+                app.insert_resource(SauvegardeAuto::new(dossier, intervalle, conservees));
+            }
+            Err(erreur) => error!(%dossier, %erreur, "{}", tr("log.dossier_autosave")),
+        }
+    }
+    if let Some(dossier) = obtenir_valeur_option("--record") {
+        match fs::create_dir_all(&dossier) {
+            Ok(()) => {
+                let intervalle = lire_option_numerique("--record-intervalle", 1, u32::MAX).unwrap_or(INTERVALLE_CAPTURE);
+                app.insert_resource(EnregistrementImages::new(dossier, intervalle));
+            }
+            Err(erreur) => error!(%dossier, %erreur, "{}", tr("log.dossier_enregistrement")),
+        }
+    }
+
+    app.run();
 }
 
-/// Ressource stockant la seed
-#[derive(Resource)]
-struct SeedCarte {
-    seed: u64,
+/// Installe l'abonné tracing qui affiche les journaux dans la console
+/// `--log-level` choisit le niveau minimal (`RUST_LOG` sinon) et `--log-json` produit une ligne JSON par événement
+fn initialiser_journalisation() {
+    let niveau = obtenir_valeur_option("--log-level");
+    let niveau_valide = niveau
+        .as_deref()
+        .filter(|niveau| ["error", "warn", "info", "debug", "trace"].contains(niveau));
+
+    // wgpu et naga sont très bavards, comme dans la configuration par défaut de Bevy
+    let filtre_niveau = |niveau: &str| EnvFilter::new(format!("{},wgpu=error,naga=warn", niveau));
+    let filtre = match niveau_valide {
+        Some(niveau) => filtre_niveau(niveau),
+        // Sans --log-level, RUST_LOG est respecté comme avec le LogPlugin de Bevy
+        None if niveau.is_none() => EnvFilter::try_from_default_env().unwrap_or_else(|_| filtre_niveau("info")),
+        None => filtre_niveau("info"),
+    };
+    let abonne = tracing_subscriber::fmt().with_env_filter(filtre);
+
+    if option_presente("--log-json") {
+        abonne.json().init();
+    } else {
+        abonne.init();
+    }
+
+    if let (Some(niveau), None) = (&niveau, niveau_valide) {
+        warn!(%niveau, "{}", tr("log.niveau_inconnu"));
+    }
 }
 
 /// si une seed a été fournie en argument, sinon retourne None
@@ -64,129 +240,77 @@ fn obtenir_seed_depuis_arguments() -> Option<u64> {
     }
 }
 
-/// Génère une seed aléatoire si aucune n'est fournie
-fn generer_seed_aleatoire() -> u64 {
-    rand::thread_rng().gen::<u64>()
+/// Retourne la valeur qui suit l'option `nom` dans les arguments, si elle est présente
+fn obtenir_valeur_option(nom: &str) -> Option<String> {
+    let arguments: Vec<String> = env::args().collect();
+    arguments
+        .iter()
+        .position(|argument| argument == nom)
+        .and_then(|index| arguments.get(index + 1).cloned())
 }
 
-/// Initialise la caméra dans la simulation
-fn initialiser_map(mut commandes: Commands) {
-    commandes.spawn(Camera2dBundle::default());
+/// Indique si l'option `nom` est présente dans les arguments
+fn option_presente(nom: &str) -> bool {
+    env::args().any(|argument| argument == nom)
 }
 
-/// génère la carte avec les obstacles et les ressources
-fn generer_map(mut commandes: Commands, seed_carte: Res<SeedCarte>) {
-    println!("Seed Actuel: {}", seed_carte.seed);
-
-    let bruit_perlin = Perlin::new(seed_carte.seed as u32);
-    let mut generateur_aleatoire = StdRng::seed_from_u64(seed_carte.seed);
-
-    let mut carte = vec![vec![TypePixel::Vide; LARGEUR_CARTE]; HAUTEUR_CARTE];
-
-    // Génération des obstacles en utilisant le bruit de Perlin
-    for y in 0..HAUTEUR_CARTE {
-        for x in 0..LARGEUR_CARTE {
-            let valeur_bruit = bruit_perlin.get([x as f64 * 0.1, y as f64 * 0.1]);
-
-            if valeur_bruit > SEUIL_OBSTACLE {
-                carte[y][x] = TypePixel::Obstacle;
-            }
-        }
-    }
-
-    // Limite la taille des obstacles pour éviter des zones trop grandes
-    limiter_taille_obstacles(&mut carte);
-
-    // Ajout aléatoire des ressources sur les pixel vides
-    for y in 0..HAUTEUR_CARTE {
-        for x in 0..LARGEUR_CARTE {
-            if carte[y][x] == TypePixel::Vide {
-                carte[y][x] = match generateur_aleatoire.gen_range(0..100) {
-                    0..=5 => TypePixel::Energie,        // 6% de chance
-                    6..=10 => TypePixel::Minerai,      // 5% de chance
-                    11..=14 => TypePixel::SiteScientifique, // 4% de chance
-                    _ => TypePixel::Vide,
-                };
-            }
+/// Lit une option numérique comprise entre `min` et `max`, ou retourne None si elle est absente ou invalide
+fn lire_option_numerique<T>(nom: &str, min: T, max: T) -> Option<T>
+where
+    T: FromStr + PartialOrd + Display + Copy,
+{
+    let valeur = obtenir_valeur_option(nom)?;
+    match valeur.parse::<T>() {
+        Ok(nombre) if (min..=max).contains(&nombre) => Some(nombre),
+        _ => {
+            warn!(option = nom, %valeur, %min, %max, "{}", tr("log.valeur_invalide"));
+            None
         }
     }
+}
 
-    // Placement de la station sur une case vide
-    let (pos_x, pos_y) = placer_station(&mut carte, &mut generateur_aleatoire);
-    println!("Station placée en ({}, {})", pos_x, pos_y);
-
-    // 🔹 Création des entités Bevy pour afficher la carte
-    for y in 0..HAUTEUR_CARTE {
-        for x in 0..LARGEUR_CARTE {
-            let couleur = match carte[y][x] {
-                TypePixel::Obstacle => Color::rgb(0.2, 0.2, 0.2),
-                TypePixel::Energie => Color::rgb(1.0, 1.0, 0.0),
-                TypePixel::Minerai => Color::rgb(0.5, 0.3, 0.1),
-                TypePixel::SiteScientifique => Color::rgb(0.0, 0.8, 0.8),
-                TypePixel::Station => Color::rgb(1.0, 0.0, 0.0), // 🔴 Station en rouge
-                TypePixel::Vide => Color::rgb(0.8, 0.8, 0.8),
-            };
-
-            commandes.spawn(SpriteBundle {
-                sprite: Sprite {
-                    color: couleur,
-                    custom_size: Some(Vec2::splat(TAILLE_CASE)),
-                    ..Default::default()
-                },
-                transform: Transform::from_translation(Vec3::new(
-                    x as f32 * TAILLE_CASE - (LARGEUR_CARTE as f32 * TAILLE_CASE) / 2.0,
-                    y as f32 * TAILLE_CASE - (HAUTEUR_CARTE as f32 * TAILLE_CASE) / 2.0,
-                    0.0,
-                )),
-                ..Default::default()
-            })
-                .insert(Pixel { type_pixel: carte[y][x] });
-        }
+/// Difficulté passée avec --difficulte, Normal par défaut
+fn lire_difficulte() -> Difficulte {
+    match obtenir_valeur_option("--difficulte") {
+        Some(nom) => Difficulte::depuis_nom(&nom).unwrap_or_else(|| {
+            warn!(%nom, "{}", tr("log.difficulte_inconnue"));
+            Difficulte::Normal
+        }),
+        None => Difficulte::Normal,
     }
 }
 
-/// Place une station sur une case vide de la map
-fn placer_station(carte: &mut Vec<Vec<TypePixel>>, generateur_aleatoire: &mut StdRng) -> (usize, usize) {
-    loop {
-        let x = generateur_aleatoire.gen_range(0..LARGEUR_CARTE);
-        let y = generateur_aleatoire.gen_range(0..HAUTEUR_CARTE);
-
-        if carte[y][x] == TypePixel::Vide {
-            carte[y][x] = TypePixel::Station;
-            return (x, y);
+/// Réglages de génération passés en ligne de commande, appliqués à chaque partie
+fn lire_reglages_generation() -> ReglagesGeneration {
+    let placement_station = obtenir_valeur_option("--placement-station").and_then(|nom| {
+        let placement = PlacementStation::depuis_nom(&nom);
+        if placement.is_none() {
+            warn!(%nom, "{}", tr("log.placement_inconnu"));
         }
+        placement
+    });
+
+    ReglagesGeneration {
+        nb_stations: lire_option_numerique("--stations", 1, MAX_STATIONS),
+        rayon_zone_sure: lire_option_numerique("--rayon-zone-sure", 0, MAX_RAYON_ZONE_SURE),
+        seuil_obstacle: lire_option_numerique("--seuil-obstacle", -1.0, 1.0),
+        echelle_bruit: lire_option_numerique("--echelle-bruit", 0.01, 1.0),
+        placement_station,
+        torique: option_presente("--wrap"),
     }
 }
 
-/// Fonction limitant la taille des obstacles pour éviter des regroupements trop larges
-fn limiter_taille_obstacles(carte: &mut Vec<Vec<TypePixel>>) {
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-
-    for y in 0..HAUTEUR_CARTE {
-        for x in 0..LARGEUR_CARTE {
-            if carte[y][x] == TypePixel::Obstacle {
-                let mut taille_obstacle = 1;
-
-                for (dx, dy) in directions.iter() {
-                    let mut nx = x as isize + dx;
-                    let mut ny = y as isize + dy;
-
-                    while nx >= 0
-                        && nx < LARGEUR_CARTE as isize
-                        && ny >= 0
-                        && ny < HAUTEUR_CARTE as isize
-                        && carte[ny as usize][nx as usize] == TypePixel::Obstacle
-                    {
-                        taille_obstacle += 1;
-                        if taille_obstacle > MAX_TAILLE_OBSTACLE {
-                            carte[ny as usize][nx as usize] = TypePixel::Vide;
-                        }
-
-                        nx += dx;
-                        ny += dy;
-                    }
-                }
-            }
+/// Thème de couleurs passé avec --couleurs : nom d'un thème prédéfini ou fichier TOML
+fn lire_theme_couleurs() -> ThemeCouleurs {
+    let Some(valeur) = obtenir_valeur_option("--couleurs") else {
+        return ThemeCouleurs::par_defaut();
+    };
+
+    ThemeCouleurs::depuis_nom(&valeur).unwrap_or_else(|| match ThemeCouleurs::charger(&valeur) {
+        Ok(theme) => theme,
+        Err(erreur) => {
+            error!(fichier = %valeur, %erreur, "{}", tr("log.theme_impossible"));
+            ThemeCouleurs::par_defaut()
         }
-    }
+    })
 }
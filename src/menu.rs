@@ -0,0 +1,301 @@
+use crate::carte::{Difficulte, ReglagesGeneration};
+use crate::langue::tr;
+use crate::sauvegarde::Sauvegarde;
+use crate::simulation::{EtatApplication, HorlogeSimulation, SeedCarte, TickSimulation, DUREE_PHASE};
+use crate::theme::ThemeCouleurs;
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+
+// Nombre maximal de chiffres d'une seed (u64::MAX en compte 20)
+const LONGUEUR_MAX_SEED: usize = 20;
+
+/// Ressource contenant les choix faits dans le menu pour la prochaine partie
+#[derive(Resource)]
+pub struct ConfigurationPartie {
+    // Seed tapée par l'utilisateur, vide pour une seed aléatoire
+    pub saisie_seed: String,
+    pub difficulte: Difficulte,
+    // Réglages de la ligne de commande appliqués à chaque partie
+    pub reglages: ReglagesGeneration,
+    // Carte passée avec --charger, utilisée si la seed saisie est la sienne
+    pub sauvegarde: Option<Sauvegarde>,
+}
+
+/// Composant Bevy marquant la racine d'un écran de menu (départ ou fin de partie)
+#[derive(Component)]
+pub struct EcranMenu;
+
+/// Composant Bevy marquant le texte affichant la seed saisie
+#[derive(Component)]
+pub struct TexteSeed;
+
+/// Action déclenchée par un bouton du menu
+#[derive(Component, Clone, Copy)]
+pub enum BoutonMenu {
+    Difficulte(Difficulte),
+    Demarrer,
+    // Retourne au menu avec la seed de la partie terminée
+    Rejouer,
+    RetourMenu,
+}
+
+/// Crée l'écran de menu : saisie de la seed, choix de la difficulté et bouton de démarrage
+pub fn creer_menu(mut commandes: Commands, configuration: Res<ConfigurationPartie>, couleurs: Res<ThemeCouleurs>) {
+    let style_texte = TextStyle {
+        font_size: 24.0,
+        color: couleurs.interface.texte,
+        ..Default::default()
+    };
+
+    commandes
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(16.0),
+                    ..Default::default()
+                },
+                background_color: couleurs.interface.fond_menu.into(),
+                ..Default::default()
+            },
+            EcranMenu,
+        ))
+        .with_children(|ecran| {
+            ecran.spawn(TextBundle::from_section(
+                tr("menu.titre"),
+                TextStyle {
+                    font_size: 40.0,
+                    ..style_texte.clone()
+                },
+            ));
+            ecran.spawn((
+                TextBundle::from_section(texte_seed(&configuration.saisie_seed), style_texte.clone()),
+                TexteSeed,
+            ));
+
+            ecran
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(8.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|ligne| {
+                    for difficulte in [Difficulte::Facile, Difficulte::Normal, Difficulte::Difficile] {
+                        let libelle = libelle_difficulte(difficulte);
+                        creer_bouton(ligne, BoutonMenu::Difficulte(difficulte), libelle, &style_texte, &couleurs);
+                    }
+                });
+
+            creer_bouton(ecran, BoutonMenu::Demarrer, tr("menu.demarrer"), &style_texte, &couleurs);
+        });
+}
+
+/// Ajoute un bouton du menu portant le libellé donné
+fn creer_bouton(
+    parent: &mut ChildBuilder,
+    bouton: BoutonMenu,
+    libelle: &str,
+    style_texte: &TextStyle,
+    couleurs: &ThemeCouleurs,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: couleurs.interface.bouton.into(),
+                ..Default::default()
+            },
+            bouton,
+        ))
+        .with_children(|bouton| {
+            bouton.spawn(TextBundle::from_section(libelle, style_texte.clone()));
+        });
+}
+
+/// Nom d'une difficulté tel qu'affiché dans les menus
+fn libelle_difficulte(difficulte: Difficulte) -> &'static str {
+    match difficulte {
+        Difficulte::Facile => tr("menu.facile"),
+        Difficulte::Normal => tr("menu.normal"),
+        Difficulte::Difficile => tr("menu.difficile"),
+    }
+}
+
+/// Texte affiché pour la seed saisie
+fn texte_seed(saisie: &str) -> String {
+    let seed = if saisie.is_empty() { tr("menu.seed_aleatoire") } else { saisie };
+    format!("{}{}{}", tr("hud.seed"), tr("deux_points"), seed)
+}
+
+/// Ajoute les chiffres tapés au clavier à la seed (Retour arrière pour effacer)
+pub fn saisir_seed(
+    mut caracteres: EventReader<ReceivedCharacter>,
+    clavier: Res<Input<KeyCode>>,
+    mut configuration: ResMut<ConfigurationPartie>,
+    mut textes: Query<&mut Text, With<TexteSeed>>,
+) {
+    let mut saisie = configuration.saisie_seed.clone();
+    for caractere in caracteres.read() {
+        if caractere.char.is_ascii_digit() && saisie.len() < LONGUEUR_MAX_SEED {
+            saisie.push(caractere.char);
+        }
+    }
+    if clavier.just_pressed(KeyCode::Back) {
+        saisie.pop();
+    }
+    if saisie == configuration.saisie_seed {
+        return;
+    }
+
+    for mut texte in textes.iter_mut() {
+        texte.sections[0].value = texte_seed(&saisie);
+    }
+    configuration.saisie_seed = saisie;
+}
+
+/// Applique l'action des boutons cliqués
+pub fn cliquer_boutons(
+    boutons: Query<(&Interaction, &BoutonMenu), Changed<Interaction>>,
+    seed_carte: Option<Res<SeedCarte>>,
+    mut configuration: ResMut<ConfigurationPartie>,
+    mut etat_suivant: ResMut<NextState<EtatApplication>>,
+) {
+    for (interaction, bouton) in boutons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match bouton {
+            BoutonMenu::Difficulte(difficulte) => configuration.difficulte = *difficulte,
+            BoutonMenu::Demarrer => etat_suivant.set(EtatApplication::EnCours),
+            BoutonMenu::Rejouer => {
+                if let Some(seed_carte) = &seed_carte {
+                    configuration.saisie_seed = seed_carte.seed.to_string();
+                }
+                etat_suivant.set(EtatApplication::Menu);
+            }
+            BoutonMenu::RetourMenu => etat_suivant.set(EtatApplication::Menu),
+        }
+    }
+}
+
+/// Démarre la partie avec la touche Entrée
+pub fn demarrer_avec_entree(clavier: Res<Input<KeyCode>>, mut etat_suivant: ResMut<NextState<EtatApplication>>) {
+    if clavier.just_pressed(KeyCode::Return) {
+        etat_suivant.set(EtatApplication::EnCours);
+    }
+}
+
+/// Colore les boutons selon le survol et la difficulté choisie
+pub fn colorer_boutons(
+    configuration: Res<ConfigurationPartie>,
+    couleurs: Res<ThemeCouleurs>,
+    mut boutons: Query<(&BoutonMenu, &Interaction, &mut BackgroundColor)>,
+) {
+    for (bouton, interaction, mut couleur) in boutons.iter_mut() {
+        let choisi = matches!(bouton, BoutonMenu::Difficulte(difficulte) if *difficulte == configuration.difficulte);
+        couleur.0 = if choisi {
+            couleurs.interface.bouton_choisi
+        } else if *interaction == Interaction::None {
+            couleurs.interface.bouton
+        } else {
+            couleurs.interface.bouton_survole
+        };
+    }
+}
+
+/// Crée l'écran de fin de partie : seed, difficulté, durée et nombre de ticks de la partie
+pub fn creer_resume(
+    mut commandes: Commands,
+    seed_carte: Res<SeedCarte>,
+    tick: Res<TickSimulation>,
+    horloge: Res<HorlogeSimulation>,
+    configuration: Res<ConfigurationPartie>,
+    couleurs: Res<ThemeCouleurs>,
+) {
+    let style_texte = TextStyle {
+        font_size: 24.0,
+        color: couleurs.interface.texte,
+        ..Default::default()
+    };
+    let secondes = horloge.temps_ecoule.as_secs();
+    let cycles = horloge.temps_ecoule.as_secs_f32() / (2.0 * DUREE_PHASE);
+
+    commandes
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(16.0),
+                    ..Default::default()
+                },
+                background_color: couleurs.interface.fond_resume.into(),
+                // Au-dessus de l'interface de la partie, qui reste affichée derrière
+                z_index: ZIndex::Global(2),
+                ..Default::default()
+            },
+            EcranMenu,
+        ))
+        .with_children(|ecran| {
+            ecran.spawn(TextBundle::from_section(
+                tr("resume.titre"),
+                TextStyle {
+                    font_size: 40.0,
+                    ..style_texte.clone()
+                },
+            ));
+            for ligne in [
+                format!("{}{}{}", tr("hud.seed"), tr("deux_points"), seed_carte.seed),
+                format!(
+                    "{}{}{}",
+                    tr("resume.difficulte"),
+                    tr("deux_points"),
+                    libelle_difficulte(configuration.difficulte)
+                ),
+                format!(
+                    "{}{}{} min {} s ({} {})",
+                    tr("resume.duree"),
+                    tr("deux_points"),
+                    secondes / 60,
+                    secondes % 60,
+                    cycles.floor(),
+                    tr("resume.cycles")
+                ),
+                format!("{}{}{}", tr("resume.ticks"), tr("deux_points"), tick.0),
+            ] {
+                ecran.spawn(TextBundle::from_section(ligne, style_texte.clone()));
+            }
+
+            ecran
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(8.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|ligne| {
+                    creer_bouton(ligne, BoutonMenu::Rejouer, tr("resume.rejouer"), &style_texte, &couleurs);
+                    creer_bouton(ligne, BoutonMenu::RetourMenu, tr("resume.menu"), &style_texte, &couleurs);
+                });
+        });
+}
+
+/// Supprime l'écran de menu ou de fin de partie
+pub fn supprimer_menu(mut commandes: Commands, ecrans: Query<Entity, With<EcranMenu>>) {
+    for ecran in ecrans.iter() {
+        commandes.entity(ecran).despawn_recursive();
+    }
+}
@@ -0,0 +1,235 @@
+use crate::carte::Carte;
+use crate::langue::tr;
+use crate::simulation::{HorlogeSimulation, SeedCarte, TickSimulation};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::time::Duration;
+
+// Nombre de ticks entre deux sauvegardes automatiques par défaut avec --autosave (une minute à vitesse x1)
+pub const INTERVALLE_AUTOSAVE: u64 = 3840;
+
+// Nombre de sauvegardes automatiques gardées par défaut
+pub const AUTOSAVES_CONSERVEES: usize = 3;
+
+/// Contenu d'un fichier de sauvegarde
+#[derive(Serialize, Deserialize)]
+pub struct Sauvegarde {
+    pub seed: u64,
+    pub carte: Carte,
+    // Avancement de la simulation (absent des sauvegardes plus anciennes, qui repartent du début)
+    #[serde(default)]
+    pub tick: u64,
+    #[serde(default)]
+    pub temps_ecoule: Duration,
+}
+
+/// Ressource indiquant le fichier dans lequel sauvegarder la carte à la fermeture
+#[derive(Resource)]
+pub struct FichierSauvegarde {
+    pub chemin: String,
+}
+
+/// Ressource décrivant les sauvegardes automatiques faites pendant la partie
+#[derive(Resource)]
+pub struct SauvegardeAuto {
+    dossier: String,
+    // Nombre de ticks de simulation entre deux sauvegardes
+    intervalle: u64,
+    // Nombre de sauvegardes gardées, les plus anciennes sont supprimées
+    conservees: usize,
+    // Tick à partir duquel la prochaine sauvegarde est écrite
+    prochain_tick: u64,
+    // Fichiers écrits depuis le lancement, du plus ancien au plus récent
+    fichiers: VecDeque<String>,
+}
+
+impl SauvegardeAuto {
+    /// Sauvegardes dans un dossier existant, toutes les `intervalle` ticks
+    pub fn new(dossier: String, intervalle: u64, conservees: usize) -> SauvegardeAuto {
+        SauvegardeAuto {
+            dossier,
+            intervalle,
+            conservees,
+            prochain_tick: intervalle,
+            fichiers: VecDeque::new(),
+        }
+    }
+
+    /// Ecrit `autosave_{tick}.ron` puis supprime les sauvegardes en trop, et retourne le chemin écrit
+    fn enregistrer(&mut self, sauvegarde: &Sauvegarde) -> Result<String, String> {
+        let chemin = format!("{}/autosave_{}.ron", self.dossier, sauvegarde.tick);
+        sauvegarder(&chemin, sauvegarde)?;
+        self.fichiers.push_back(chemin.clone());
+
+        while self.fichiers.len() > self.conservees {
+            if let Some(ancien) = self.fichiers.pop_front() {
+                if let Err(erreur) = fs::remove_file(&ancien) {
+                    warn!(fichier = %ancien, %erreur, "{}", tr("log.autosave_suppression"));
+                }
+            }
+        }
+
+        Ok(chemin)
+    }
+}
+
+/// Ecrit la sauvegarde au format RON dans le fichier donné
+pub fn sauvegarder(chemin: &str, sauvegarde: &Sauvegarde) -> Result<(), String> {
+    let contenu = ron::ser::to_string_pretty(sauvegarde, PrettyConfig::default())
+        .map_err(|erreur| erreur.to_string())?;
+    fs::write(chemin, contenu).map_err(|erreur| erreur.to_string())
+}
+
+/// Lit une sauvegarde au format RON depuis le fichier donné
+pub fn charger(chemin: &str) -> Result<Sauvegarde, String> {
+    let contenu = fs::read_to_string(chemin).map_err(|erreur| erreur.to_string())?;
+    let sauvegarde: Sauvegarde = ron::from_str(&contenu).map_err(|erreur| erreur.to_string())?;
+
+    if !sauvegarde.carte.dimensions_valides() {
+        return Err(tr("erreur.dimensions").to_string());
+    }
+
+    Ok(sauvegarde)
+}
+
+/// Sauvegarde la carte dans le fichier demandé lorsque l'application se ferme
+pub fn sauvegarder_a_la_sortie(
+    mut sortie: EventReader<AppExit>,
+    fichier: Option<Res<FichierSauvegarde>>,
+    seed_carte: Option<Res<SeedCarte>>,
+    carte: Option<Res<Carte>>,
+    tick: Option<Res<TickSimulation>>,
+    horloge: Option<Res<HorlogeSimulation>>,
+) {
+    if sortie.read().next().is_none() {
+        return;
+    }
+
+    // Rien à sauvegarder si l'application est fermée depuis le menu
+    if let (Some(fichier), Some(seed_carte), Some(carte)) = (fichier, seed_carte, carte) {
+        let sauvegarde = Sauvegarde {
+            seed: seed_carte.seed,
+            carte: carte.clone(),
+            tick: tick.map_or(0, |tick| tick.0),
+            temps_ecoule: horloge.map_or(Duration::ZERO, |horloge| horloge.temps_ecoule),
+        };
+
+        match sauvegarder(&fichier.chemin, &sauvegarde) {
+            Ok(()) => info!(fichier = %fichier.chemin, "{}", tr("log.carte_sauvegardee")),
+            Err(erreur) => error!(fichier = %fichier.chemin, %erreur, "{}", tr("log.sauvegarde_impossible")),
+        }
+    }
+}
+
+/// Sauvegarde la partie toutes les `intervalle` ticks dans le dossier des sauvegardes automatiques
+pub fn sauvegarder_automatiquement(
+    sauvegarde_auto: Option<ResMut<SauvegardeAuto>>,
+    seed_carte: Res<SeedCarte>,
+    carte: Option<Res<Carte>>,
+    tick: Res<TickSimulation>,
+    horloge: Res<HorlogeSimulation>,
+) {
+    let (Some(mut sauvegarde_auto), Some(carte)) = (sauvegarde_auto, carte) else {
+        return;
+    };
+
+    // Le compteur de ticks repart à chaque nouvelle partie (de zéro, ou du tick d'une sauvegarde)
+    if tick.is_added() {
+        sauvegarde_auto.prochain_tick = tick.0 + sauvegarde_auto.intervalle;
+    }
+    if tick.0 < sauvegarde_auto.prochain_tick {
+        return;
+    }
+    sauvegarde_auto.prochain_tick = tick.0 + sauvegarde_auto.intervalle;
+
+    let sauvegarde = Sauvegarde {
+        seed: seed_carte.seed,
+        carte: carte.clone(),
+        tick: tick.0,
+        temps_ecoule: horloge.temps_ecoule,
+    };
+    match sauvegarde_auto.enregistrer(&sauvegarde) {
+        Ok(fichier) => info!(tick = tick.0, %fichier, "{}", tr("log.autosave")),
+        Err(erreur) => error!(tick = tick.0, %erreur, "{}", tr("log.sauvegarde_impossible")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::carte::{generer_carte, Difficulte, ParametresGeneration, TypePixel};
+    use std::env;
+
+    /// Chemin d'un fichier temporaire propre au test
+    fn fichier_temporaire(nom: &str) -> String {
+        let chemin = env::temp_dir().join(format!("{}_{}.ron", nom, std::process::id()));
+        chemin.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn sauvegarde_puis_chargement() {
+        let chemin = fichier_temporaire("sauvegarde_puis_chargement");
+        let mut parametres = ParametresGeneration::depuis_difficulte(Difficulte::Difficile);
+        parametres.nb_stations = 3;
+        let sauvegarde = Sauvegarde {
+            seed: 42,
+            carte: generer_carte(42, &parametres),
+            tick: 1500,
+            temps_ecoule: Duration::from_secs_f64(1500.0 / 64.0),
+        };
+
+        sauvegarder(&chemin, &sauvegarde).unwrap();
+        let chargee = charger(&chemin);
+        fs::remove_file(&chemin).unwrap();
+
+        let chargee = chargee.unwrap();
+        assert_eq!(chargee.seed, 42);
+        assert_eq!(chargee.tick, 1500);
+        assert_eq!(chargee.temps_ecoule, sauvegarde.temps_ecoule);
+        let cases: Vec<(_, TypePixel)> = chargee.carte.cases().collect();
+        assert_eq!(cases, sauvegarde.carte.cases().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sauvegardes_automatiques_les_plus_anciennes_supprimees() {
+        let dossier = fichier_temporaire("sauvegardes_automatiques");
+        fs::create_dir_all(&dossier).unwrap();
+        let mut sauvegarde_auto = SauvegardeAuto::new(dossier.clone(), 100, 2);
+        let mut sauvegarde = Sauvegarde {
+            seed: 1,
+            carte: Carte::new(false),
+            tick: 0,
+            temps_ecoule: Duration::ZERO,
+        };
+
+        for tick in [100, 200, 300] {
+            sauvegarde.tick = tick;
+            sauvegarde_auto.enregistrer(&sauvegarde).unwrap();
+        }
+        let mut restantes: Vec<String> = fs::read_dir(&dossier)
+            .unwrap()
+            .map(|entree| entree.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        restantes.sort();
+        let rechargee = charger(&format!("{}/autosave_300.ron", dossier));
+        fs::remove_dir_all(&dossier).unwrap();
+
+        assert_eq!(restantes, vec!["autosave_200.ron", "autosave_300.ron"]);
+        assert_eq!(rechargee.unwrap().tick, 300);
+    }
+
+    #[test]
+    fn chargement_refuse_un_mauvais_nombre_de_cases() {
+        let chemin = fichier_temporaire("mauvais_nombre_de_cases");
+        fs::write(&chemin, "(seed: 7, carte: (donnees: [Vide, Obstacle, Station], torique: false))").unwrap();
+
+        let resultat = charger(&chemin);
+        fs::remove_file(&chemin).unwrap();
+
+        assert_eq!(resultat.err(), Some(tr("erreur.dimensions").to_string()));
+    }
+}
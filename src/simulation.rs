@@ -0,0 +1,236 @@
+use crate::carte::{Carte, ParametresGeneration};
+use crate::langue::tr;
+use crate::menu::ConfigurationPartie;
+use bevy::prelude::*;
+use rand::prelude::*;
+use std::time::Duration;
+
+// Durée d'une phase (jour ou nuit) en secondes
+pub const DUREE_PHASE: f32 = 20.0;
+
+// Nombre de ticks de simulation par seconde à vitesse x1 (fréquence de FixedUpdate)
+pub const TICKS_PAR_SECONDE: f64 = 64.0;
+
+// Bornes du multiplicateur de vitesse de la simulation
+const VITESSE_MIN: f32 = 0.25;
+const VITESSE_MAX: f32 = 8.0;
+
+/// Enumération des phases du cycle jour/nuit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhaseJournee {
+    Jour,
+    Nuit,
+}
+
+/// États de l'application : menu de départ, partie en cours ou en pause, et résumé de fin de partie
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EtatApplication {
+    #[default]
+    Menu,
+    EnCours,
+    Pause,
+    FinDePartie,
+}
+
+/// Composant Bevy marquant les entités d'une partie, supprimées au retour au menu
+#[derive(Component)]
+pub struct EntitePartie;
+
+/// Ressource stockant la seed
+#[derive(Resource)]
+pub struct SeedCarte {
+    pub seed: u64,
+}
+
+/// Ressource représentant l'horloge globale qui pilote le cycle jour/nuit
+#[derive(Resource)]
+pub struct HorlogeSimulation {
+    pub minuterie: Timer,
+    pub phase: PhaseJournee,
+    // Temps de simulation écoulé depuis le début de la partie
+    pub temps_ecoule: Duration,
+}
+
+impl HorlogeSimulation {
+    /// Horloge après `temps_ecoule` de simulation : la phase et la minuterie s'en déduisent
+    pub fn depuis_temps_ecoule(temps_ecoule: Duration) -> HorlogeSimulation {
+        let duree_phase = Duration::from_secs_f32(DUREE_PHASE);
+        let phases_terminees = temps_ecoule.as_nanos() / duree_phase.as_nanos();
+
+        let mut minuterie = Timer::new(duree_phase, TimerMode::Repeating);
+        minuterie.set_elapsed(temps_ecoule - duree_phase * phases_terminees as u32);
+
+        HorlogeSimulation {
+            minuterie,
+            phase: match phases_terminees % 2 {
+                0 => PhaseJournee::Jour,
+                _ => PhaseJournee::Nuit,
+            },
+            temps_ecoule,
+        }
+    }
+}
+
+/// Ressource comptant les ticks de simulation écoulés depuis le début de la partie
+#[derive(Resource, Default)]
+pub struct TickSimulation(pub u64);
+
+/// Ressource réglant l'écoulement du temps de la simulation
+#[derive(Resource)]
+pub struct VitesseSimulation {
+    // Nombre de ticks joués à chaque pas fixe (fractionnaire en dessous de x1)
+    pub multiplicateur: f32,
+}
+
+/// Indique si une partie est affichée, en cours ou en pause
+pub fn partie_affichee(etat: Res<State<EtatApplication>>) -> bool {
+    matches!(etat.get(), EtatApplication::EnCours | EtatApplication::Pause)
+}
+
+/// Prépare la seed, les paramètres et l'horloge de la partie à partir des choix du menu
+pub fn demarrer_partie(
+    mut commandes: Commands,
+    mut configuration: ResMut<ConfigurationPartie>,
+    mut vitesse: ResMut<VitesseSimulation>,
+) {
+    let seed = configuration
+        .saisie_seed
+        .parse::<u64>()
+        .unwrap_or_else(|_| generer_seed_aleatoire());
+    info!(seed, "{}", tr("log.seed"));
+
+    // La carte chargée n'est utilisée que si la seed n'a pas été changée dans le menu
+    // La partie reprend alors au tick et à l'heure de la sauvegarde
+    let (tick, temps_ecoule) = match configuration.sauvegarde.take() {
+        Some(sauvegarde) if sauvegarde.seed == seed => {
+            commandes.insert_resource(sauvegarde.carte);
+            (sauvegarde.tick, sauvegarde.temps_ecoule)
+        }
+        _ => (0, Duration::ZERO),
+    };
+
+    let difficulte = configuration.difficulte;
+    info!(?difficulte, "{}", tr("log.difficulte"));
+
+    commandes.insert_resource(SeedCarte { seed }); // Stocke la seed pour garantir une génération reproductible
+    commandes.insert_resource(ParametresGeneration::avec_reglages(difficulte, &configuration.reglages));
+    // Chaque partie commence à vitesse normale, quelle que soit celle de la précédente
+    vitesse.multiplicateur = 1.0;
+    commandes.insert_resource(TickSimulation(tick));
+    commandes.insert_resource(HorlogeSimulation::depuis_temps_ecoule(temps_ecoule));
+}
+
+/// Génère une seed aléatoire si aucune n'est fournie
+fn generer_seed_aleatoire() -> u64 {
+    rand::thread_rng().gen::<u64>()
+}
+
+/// Retourne au menu avec la touche Échap
+pub fn retourner_au_menu(clavier: Res<Input<KeyCode>>, mut etat_suivant: ResMut<NextState<EtatApplication>>) {
+    if clavier.just_pressed(KeyCode::Escape) {
+        etat_suivant.set(EtatApplication::Menu);
+    }
+}
+
+/// Termine la partie avec la touche F10 (loin des touches de la caméra) et affiche son résumé
+pub fn terminer_partie(clavier: Res<Input<KeyCode>>, mut etat_suivant: ResMut<NextState<EtatApplication>>) {
+    if clavier.just_pressed(KeyCode::F10) {
+        etat_suivant.set(EtatApplication::FinDePartie);
+    }
+}
+
+/// Supprime les entités et la carte de la partie terminée, et replace la caméra
+pub fn nettoyer_partie(
+    mut commandes: Commands,
+    entites: Query<Entity, With<EntitePartie>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    for entite in entites.iter() {
+        commandes.entity(entite).despawn_recursive();
+    }
+    commandes.remove_resource::<Carte>();
+
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        transform.translation = Vec3::new(0.0, 0.0, transform.translation.z);
+        projection.scale = 1.0;
+    }
+}
+
+/// Met en pause (Espace), avance d'un tick pendant la pause (.) ou change la vitesse de la simulation (+ / -)
+pub fn controler_vitesse(
+    clavier: Res<Input<KeyCode>>,
+    etat: Res<State<EtatApplication>>,
+    mut etat_suivant: ResMut<NextState<EtatApplication>>,
+    mut vitesse: ResMut<VitesseSimulation>,
+    mut tick: ResMut<TickSimulation>,
+    mut horloge: ResMut<HorlogeSimulation>,
+) {
+    if clavier.just_pressed(KeyCode::Space) {
+        let suivant = match etat.get() {
+            EtatApplication::Pause => EtatApplication::EnCours,
+            _ => EtatApplication::Pause,
+        };
+        info!(etat = ?suivant, "{}", tr("log.pause"));
+        etat_suivant.set(suivant);
+    }
+
+    if *etat.get() == EtatApplication::Pause && clavier.any_just_pressed([KeyCode::Period, KeyCode::NumpadDecimal]) {
+        jouer_tick(&mut tick, &mut horloge);
+        debug!(tick = tick.0, "{}", tr("log.pas_a_pas"));
+    }
+
+    let multiplicateur = if clavier.any_just_pressed([KeyCode::Plus, KeyCode::Equals, KeyCode::NumpadAdd]) {
+        vitesse.multiplicateur * 2.0
+    } else if clavier.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        vitesse.multiplicateur / 2.0
+    } else {
+        return;
+    };
+    vitesse.multiplicateur = multiplicateur.clamp(VITESSE_MIN, VITESSE_MAX);
+    info!(multiplicateur = vitesse.multiplicateur, "{}", tr("log.vitesse"));
+}
+
+/// Joue à chaque pas fixe autant de ticks que le permet la vitesse de la simulation
+pub fn avancer_simulation(
+    vitesse: Res<VitesseSimulation>,
+    mut ticks_en_attente: Local<f32>,
+    mut tick: ResMut<TickSimulation>,
+    mut horloge: ResMut<HorlogeSimulation>,
+) {
+    *ticks_en_attente += vitesse.multiplicateur;
+    while *ticks_en_attente >= 1.0 {
+        *ticks_en_attente -= 1.0;
+        jouer_tick(&mut tick, &mut horloge);
+    }
+}
+
+/// Avance d'un tick : fait avancer l'horloge globale et bascule entre le jour et la nuit
+fn jouer_tick(tick: &mut TickSimulation, horloge: &mut HorlogeSimulation) {
+    let ecoule = Duration::from_secs_f64(1.0 / TICKS_PAR_SECONDE);
+    tick.0 += 1;
+    horloge.temps_ecoule += ecoule;
+    horloge.minuterie.tick(ecoule);
+
+    if horloge.minuterie.just_finished() {
+        horloge.phase = match horloge.phase {
+            PhaseJournee::Jour => PhaseJournee::Nuit,
+            PhaseJournee::Nuit => PhaseJournee::Jour,
+        };
+        info!(tick = tick.0, phase = ?horloge.phase, "{}", tr("log.changement_phase"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn horloge_reprise_au_milieu_d_une_nuit() {
+        let temps_ecoule = Duration::from_secs_f32(DUREE_PHASE * 1.5);
+        let horloge = HorlogeSimulation::depuis_temps_ecoule(temps_ecoule);
+
+        assert_eq!(horloge.phase, PhaseJournee::Nuit);
+        assert_eq!(horloge.minuterie.remaining(), Duration::from_secs_f32(DUREE_PHASE / 2.0));
+        assert_eq!(HorlogeSimulation::depuis_temps_ecoule(Duration::ZERO).phase, PhaseJournee::Jour);
+    }
+}
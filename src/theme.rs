@@ -0,0 +1,197 @@
+use crate::carte::TypePixel;
+use crate::langue::tr;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use toml_edit::Document;
+
+/// Ressource regroupant les couleurs d'affichage
+/// Tout ce qui est dessiné (sprites, minimap, légende, fond, interface et menus) lit ses couleurs ici
+#[derive(Resource, Clone)]
+pub struct ThemeCouleurs {
+    cases: HashMap<TypePixel, Color>,
+    // Couleur de fond de la fenêtre, autour de la carte
+    pub fond: Color,
+    pub interface: CouleursInterface,
+}
+
+/// Couleurs de l'interface et des menus, communes aux thèmes prédéfinis
+#[derive(Clone)]
+pub struct CouleursInterface {
+    pub texte: Color,
+    // Fond des panneaux affichés par-dessus la carte (barre d'informations, légende, débogage)
+    pub fond_panneau: Color,
+    pub fond_info_bulle: Color,
+    pub bordure_minimap: Color,
+    // Rectangle de la minimap indiquant la zone visible par la caméra
+    pub cadre_vue: Color,
+    pub fond_menu: Color,
+    // Fond du résumé de fin de partie, qui laisse voir la carte derrière lui
+    pub fond_resume: Color,
+    pub bouton: Color,
+    pub bouton_survole: Color,
+    pub bouton_choisi: Color,
+}
+
+impl Default for CouleursInterface {
+    fn default() -> CouleursInterface {
+        CouleursInterface {
+            texte: Color::WHITE,
+            fond_panneau: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            fond_info_bulle: Color::rgba(0.0, 0.0, 0.0, 0.8),
+            bordure_minimap: Color::BLACK,
+            cadre_vue: Color::WHITE,
+            fond_menu: Color::rgb(0.1, 0.1, 0.1),
+            fond_resume: Color::rgba(0.1, 0.1, 0.1, 0.9),
+            bouton: Color::rgb(0.25, 0.25, 0.25),
+            bouton_survole: Color::rgb(0.35, 0.35, 0.35),
+            bouton_choisi: Color::rgb(0.2, 0.5, 0.2),
+        }
+    }
+}
+
+impl CouleursInterface {
+    /// Couleur de l'interface portant ce nom dans un fichier de thème
+    fn couleur_mut(&mut self, nom: &str) -> Option<&mut Color> {
+        match nom {
+            "texte" => Some(&mut self.texte),
+            "fond_panneau" => Some(&mut self.fond_panneau),
+            "fond_info_bulle" => Some(&mut self.fond_info_bulle),
+            "bordure_minimap" => Some(&mut self.bordure_minimap),
+            "cadre_vue" => Some(&mut self.cadre_vue),
+            "fond_menu" => Some(&mut self.fond_menu),
+            "fond_resume" => Some(&mut self.fond_resume),
+            "bouton" => Some(&mut self.bouton),
+            "bouton_survole" => Some(&mut self.bouton_survole),
+            "bouton_choisi" => Some(&mut self.bouton_choisi),
+            _ => None,
+        }
+    }
+}
+
+impl ThemeCouleurs {
+    /// Couleurs d'origine du projet (voir le code couleurs du README)
+    pub fn par_defaut() -> ThemeCouleurs {
+        ThemeCouleurs {
+            cases: HashMap::from([
+                (TypePixel::Vide, Color::rgb(0.8, 0.8, 0.8)),
+                (TypePixel::Obstacle, Color::rgb(0.2, 0.2, 0.2)),
+                (TypePixel::Energie, Color::rgb(1.0, 1.0, 0.0)),
+                (TypePixel::Minerai, Color::rgb(0.5, 0.3, 0.1)),
+                (TypePixel::SiteScientifique, Color::rgb(0.0, 0.8, 0.8)),
+                (TypePixel::Station, Color::rgb(1.0, 0.0, 0.0)), // 🔴 Station en rouge
+                (TypePixel::ZoneDanger, Color::rgb(0.6, 0.1, 0.6)),
+            ]),
+            fond: Color::rgb(0.4, 0.4, 0.4),
+            interface: CouleursInterface::default(),
+        }
+    }
+
+    /// Palette d'Okabe et Ito, distinguable avec les formes courantes de daltonisme
+    pub fn daltonien() -> ThemeCouleurs {
+        ThemeCouleurs {
+            cases: HashMap::from([
+                (TypePixel::Vide, Color::rgb_u8(0xDD, 0xDD, 0xDD)),
+                (TypePixel::Obstacle, Color::rgb_u8(0x33, 0x33, 0x33)),
+                (TypePixel::Energie, Color::rgb_u8(0xF0, 0xE4, 0x42)),
+                (TypePixel::Minerai, Color::rgb_u8(0xE6, 0x9F, 0x00)),
+                (TypePixel::SiteScientifique, Color::rgb_u8(0x56, 0xB4, 0xE9)),
+                (TypePixel::Station, Color::rgb_u8(0xD5, 0x5E, 0x00)),
+                (TypePixel::ZoneDanger, Color::rgb_u8(0x00, 0x72, 0xB2)),
+            ]),
+            fond: Color::rgb(0.4, 0.4, 0.4),
+            interface: CouleursInterface::default(),
+        }
+    }
+
+    /// Retourne le thème prédéfini correspondant à son nom (defaut, daltonien)
+    pub fn depuis_nom(nom: &str) -> Option<ThemeCouleurs> {
+        match nom {
+            "defaut" => Some(ThemeCouleurs::par_defaut()),
+            "daltonien" => Some(ThemeCouleurs::daltonien()),
+            _ => None,
+        }
+    }
+
+    /// Lit un thème TOML associant un nom de case, `fond` ou un élément de l'interface à une couleur hexadécimale
+    /// Les couleurs absentes du fichier gardent leur valeur par défaut
+    pub fn charger(chemin: &str) -> Result<ThemeCouleurs, String> {
+        let contenu = fs::read_to_string(chemin).map_err(|erreur| erreur.to_string())?;
+        let document: Document = contenu.parse().map_err(|erreur: toml_edit::TomlError| erreur.to_string())?;
+        let mut theme = ThemeCouleurs::par_defaut();
+
+        for (cle, valeur) in document.iter() {
+            let texte = valeur
+                .as_str()
+                .ok_or_else(|| format!("{}{}{}", cle, tr("deux_points"), tr("erreur.couleur_attendue")))?;
+            let couleur = Color::hex(texte).map_err(|erreur| format!("{}{}{}", cle, tr("deux_points"), erreur))?;
+
+            if cle == "fond" {
+                theme.fond = couleur;
+            } else if let Some(couleur_interface) = theme.interface.couleur_mut(cle) {
+                *couleur_interface = couleur;
+            } else {
+                let type_pixel = TypePixel::TOUS
+                    .into_iter()
+                    .find(|type_pixel| type_pixel.nom() == cle)
+                    .ok_or_else(|| format!("{}{}{}", tr("erreur.case_inconnue"), tr("deux_points"), cle))?;
+                theme.cases.insert(type_pixel, couleur);
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Couleur d'affichage d'un type de pixel
+    pub fn couleur(&self, type_pixel: TypePixel) -> Color {
+        self.cases[&type_pixel]
+    }
+}
+
+/// Ressource décrivant le pack de textures utilisé pour afficher les cases
+/// Les types de pixel sans texture dans le dossier sont affichés avec leur couleur unie
+#[derive(Resource)]
+pub struct TileTheme {
+    // Dossier des textures, relatif au dossier assets
+    pub dossier: String,
+    pub textures: HashMap<TypePixel, Handle<Image>>,
+}
+
+impl TileTheme {
+    /// Thème sans texture chargée, lues dans le dossier donné au démarrage
+    pub fn new(dossier: String) -> TileTheme {
+        TileTheme {
+            dossier,
+            textures: HashMap::new(),
+        }
+    }
+
+    /// Couleur de base d'un type de pixel : blanc si une texture est disponible, sa couleur du thème sinon
+    pub fn couleur_base(&self, type_pixel: TypePixel, couleurs: &ThemeCouleurs) -> Color {
+        if self.textures.contains_key(&type_pixel) {
+            Color::WHITE
+        } else {
+            couleurs.couleur(type_pixel)
+        }
+    }
+}
+
+/// Charge les textures du thème de tuiles présentes dans le dossier assets
+pub fn charger_tile_theme(mut theme: ResMut<TileTheme>, serveur_assets: Res<AssetServer>) {
+    let dossier_assets = FileAssetReader::get_base_path().join("assets");
+
+    for type_pixel in TypePixel::TOUS {
+        let chemin = format!("{}/{}.png", theme.dossier, type_pixel.nom());
+
+        if dossier_assets.join(&chemin).exists() {
+            let texture = serveur_assets.load(chemin);
+            theme.textures.insert(type_pixel, texture);
+        }
+    }
+
+    if theme.textures.is_empty() {
+        warn!(dossier = %theme.dossier, "{}", tr("log.textures_absentes"));
+    }
+}
+